@@ -0,0 +1,93 @@
+//! Benchmarks the parts of every day on its puzzle input, and the parser of the days that are
+//! listed in `parsers!`.
+//!
+//! Run them with `cargo bench`, or `cargo bench -- day_07` for a single day. Use
+//! `cargo bench -- --save-baseline <name>` to store the results and
+//! `cargo bench -- --baseline <name>` to compare against them later.
+use advent_of_code_2020::input::{self, Source};
+use advent_of_code_2020::modules::Part;
+use advent_of_code_2020::params::Params;
+use advent_of_code_2020::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn bench_day(c: &mut Criterion, module: &str, parts: &[Part]) {
+    let input = match input::load(module, &Source::Default) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Skipping {}: {}", module, error);
+            return;
+        }
+    };
+
+    let params = Params::new();
+
+    let mut group = c.benchmark_group(module);
+    // some parts take seconds, keep the number of samples low to finish in reasonable time
+    group.sample_size(10);
+    if let Some(parse) = parser(module) {
+        group.bench_function("parse", |b| b.iter(|| parse(black_box(&input))));
+    }
+    for (index, part) in parts.iter().enumerate() {
+        group.bench_function(format!("part_{}", index + 1), |b| {
+            b.iter(|| part(black_box(&input), &params))
+        });
+    }
+    group.finish();
+}
+
+// the day modules and their parts are found by the build script, like in the library
+include!(concat!(env!("OUT_DIR"), "/day_modules.rs"));
+
+macro_rules! bench_days {
+    ($($mod:ident => [$($part:ident),*] $($params:ident)?,)*) => {
+        fn solvers(c: &mut Criterion) {
+            $( bench_day(c, stringify!($mod), &[$($mod::$part as Part),*]); )*
+        }
+    };
+}
+
+day_modules!(bench_days);
+
+macro_rules! parsers {
+    ($($mod:ident => $parse:expr,)*) => {
+        /// The parser of a day, a day without one only has its parts benchmarked
+        fn parser(module: &str) -> Option<fn(&str) -> bool> {
+            match module {
+                $( stringify!($mod) => Some(|input| black_box($parse(input)).is_ok()), )*
+                _ => None,
+            }
+        }
+    };
+}
+
+parsers![
+    day_01 => day_01::parse_expense_report,
+    day_02 => day_02::parse_passwords,
+    day_03 => day_03::parse_map,
+    day_04 => day_04::parse_passports,
+    day_05 => day_05::parse_seat_ids,
+    day_06 => day_06::load_groups_answers,
+    day_07 => day_07::parse_bag_rules,
+    day_08 => day_08::load_program,
+    day_09 => day_09::parse_xmas_data,
+    day_10 => day_10::parse_jolt_adapters,
+    day_11 => day_11::load_seats,
+    day_12 => day_12::parse_instructions,
+    day_13 => day_13::parse_bus_schedule_for_contest,
+    day_14 => day_14::parse_program,
+    day_15 => day_15::parse_start_sequence,
+    day_16 => day_16::parse_notes,
+    day_17 => day_17::parse_cubes::<day_17::Point4D>,
+    day_18 => day_18::parse_homework,
+    // the rules are the first block of the input, the messages follow
+    day_19 => |input: &str| {
+        input
+            .split_once("\n\n")
+            .ok_or_else(|| error::Error::unexpected_end(19, "a block of messages after the rules"))
+            .and_then(|(rules, _)| day_19::parse_rules(rules))
+    },
+];
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
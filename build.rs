@@ -0,0 +1,64 @@
+//! Finds the day modules in src/ so they don't have to be registered by hand.
+//!
+//! Every `src/day_NN.rs` is a module, its parts are the `pub fn part_N` functions in it. A day
+//! that takes parameters lists their names in a `pub const PARAMS`. The modules are written to `$OUT_DIR/day_modules.rs` as a `day_modules!` macro, which hands the
+//! list to the macro it is called with.
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut modules = Vec::new();
+    for entry in fs::read_dir("src").expect("Could not read the src directory") {
+        let path = entry.expect("Could not read an entry of src").path();
+        let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(name) if is_day_module(name) && path.extension() == Some("rs".as_ref()) => {
+                name.to_string()
+            }
+            _ => continue,
+        };
+        let source = fs::read_to_string(&path).expect("Could not read a day module");
+        let params = source
+            .lines()
+            .any(|line| line.starts_with("pub const PARAMS"));
+        modules.push((name, find_parts(&source), params));
+    }
+    modules.sort();
+
+    let list: Vec<_> = modules
+        .iter()
+        .map(|(name, parts, params)| {
+            let params = if *params { " PARAMS" } else { "" };
+            format!("            {} => [{}]{},", name, parts.join(", "), params)
+        })
+        .collect();
+    let day_modules = format!(
+        "macro_rules! day_modules {{\n    ($callback:ident) => {{\n        $callback![\n{}\n        ];\n    }};\n}}\n",
+        list.join("\n")
+    );
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("day_modules.rs"), day_modules)
+        .expect("Could not write the list of day modules");
+}
+
+/// Day modules are named `day_NN`
+fn is_day_module(name: &str) -> bool {
+    match name.strip_prefix("day_") {
+        Some(day) => day.len() == 2 && day.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// The names of the `pub fn part_N` functions in the source of a module, in order
+fn find_parts(source: &str) -> Vec<String> {
+    let mut parts: Vec<(u32, String)> = source
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub fn part_"))
+        .filter_map(|rest| rest.split('(').next())
+        .filter_map(|number| number.parse().ok().map(|n| (n, format!("part_{}", n))))
+        .collect();
+    parts.sort();
+    parts.into_iter().map(|(_, part)| part).collect()
+}
@@ -0,0 +1,278 @@
+use num::{BigUint, ToPrimitive};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The answer to one part of a day.
+///
+/// The solvers only fill in the label and the value, the day and part are set by the runner.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub label: String,
+    pub value: Value,
+    /// What the solver noticed about the input without it stopping the answer, like fields that
+    /// are given twice
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl Answer {
+    pub fn new(label: impl Into<String>, value: impl Into<Value>) -> Answer {
+        Answer {
+            day: 0,
+            part: 0,
+            label: label.into(),
+            value: value.into(),
+            warnings: vec![],
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: impl IntoIterator<Item = String>) -> Answer {
+        self.warnings.extend(warnings);
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! integer_values {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Value {
+                fn from(n: $int) -> Value {
+                    Value::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_values![u16, u32, u64, usize, i32, i64];
+
+/// Numbers too large for an integer value are kept as their decimal text
+impl From<BigUint> for Value {
+    fn from(n: BigUint) -> Value {
+        match n.to_i128() {
+            Some(n) => Value::Integer(n),
+            None => Value::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::Text(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    /// A summary of the answers and the time each part took
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "table" => Ok(Format::Table),
+            _ => Err(format!(
+                "Unknown format {}, expected text, json, csv or table",
+                s
+            )),
+        }
+    }
+}
+
+/// Prints answers as they come in, so slow days don't hold back the answers before them.
+pub struct Printer {
+    format: Format,
+    printed: usize,
+    total_time: Duration,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Printer {
+        match format {
+            Format::Text => (),
+            Format::Json => println!("["),
+            Format::Csv => println!("day,part,label,value"),
+            Format::Table => println!("{}", table_row("Day", "Part", "Answer", "Time")),
+        }
+        Printer {
+            format,
+            printed: 0,
+            total_time: Duration::default(),
+        }
+    }
+
+    pub fn print(&mut self, answer: &Answer, elapsed: Duration) {
+        match self.format {
+            Format::Text => println!("{}", to_text(answer)),
+            Format::Json => {
+                if self.printed > 0 {
+                    println!(",");
+                }
+                print!("  {}", to_json(answer));
+            }
+            Format::Csv => println!("{}", to_csv(answer)),
+            Format::Table => println!("{}", to_table_row(answer, elapsed)),
+        }
+        self.printed += 1;
+        self.total_time += elapsed;
+    }
+
+    pub fn finish(self) {
+        match self.format {
+            Format::Json => {
+                if self.printed > 0 {
+                    println!();
+                }
+                println!("]");
+            }
+            Format::Table => println!(
+                "{}",
+                table_row("", "", "Total", &format!("{:.2?}", self.total_time))
+            ),
+            _ => (),
+        }
+    }
+}
+
+fn to_text(answer: &Answer) -> String {
+    format!("{}: {}", answer.label, answer.value)
+}
+
+fn to_json(answer: &Answer) -> String {
+    serde_json::to_string(answer).expect("Answers can always be serialized")
+}
+
+fn to_csv(answer: &Answer) -> String {
+    format!(
+        "{},{},{},{}",
+        answer.day,
+        answer.part,
+        csv_field(&answer.label),
+        csv_field(&answer.value.to_string())
+    )
+}
+
+fn to_table_row(answer: &Answer, elapsed: Duration) -> String {
+    table_row(
+        &answer.day.to_string(),
+        &answer.part.to_string(),
+        &answer.value.to_string(),
+        &format!("{:.2?}", elapsed),
+    )
+}
+
+fn table_row(day: &str, part: &str, value: &str, time: &str) -> String {
+    format!("{:>3}  {:>4}  {:>20}  {:>10}", day, part, value, time)
+}
+
+/// Quotes a field of a CSV row when it contains a separator, quote or newline
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer() -> Answer {
+        Answer {
+            day: 5,
+            part: 1,
+            label: "The highest seat ID in the list of boarding passes is".to_string(),
+            value: Value::Integer(933),
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            to_text(&answer()),
+            "The highest seat ID in the list of boarding passes is: 933"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&answer()),
+            r#"{"day":5,"part":1,"label":"The highest seat ID in the list of boarding passes is","value":933}"#
+        );
+        let answer = answer().with_warnings(vec!["a seat is taken twice".to_string()]);
+        assert!(to_json(&answer).ends_with(r#""value":933,"warnings":["a seat is taken twice"]}"#));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut answer = answer();
+        answer.label = "The two entries are 1721 and 299, multiplied they are".to_string();
+        answer.value = Value::Text("a \"quoted\" value".to_string());
+
+        assert_eq!(
+            to_csv(&answer),
+            r#"5,1,"The two entries are 1721 and 299, multiplied they are","a ""quoted"" value""#
+        );
+    }
+
+    #[test]
+    fn test_to_table_row() {
+        assert_eq!(
+            to_table_row(&answer(), Duration::from_micros(1500)),
+            "  5     1                   933      1.50ms"
+        );
+    }
+
+    #[test]
+    fn test_value_from_big_uint() {
+        assert_eq!(Value::from(BigUint::from(933_u32)), Value::Integer(933));
+        assert_eq!(
+            Value::from(BigUint::from(u128::MAX)),
+            Value::Text(u128::MAX.to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
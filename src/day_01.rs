@@ -49,59 +49,216 @@
 ///
 /// In your expense report, what is the product of the three entries that sum to
 /// 2020?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
 
-const INPUT: &str = include_str!("../input/day_01.txt");
+pub mod subset_sum;
 
-pub fn run() {
+const DAY: u8 = 1;
+
+/// The sum to look for and how many entries have to add up to it
+pub const PARAMS: &[&str] = &["target", "k"];
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        1721\n\
+        979\n\
+        366\n\
+        299\n\
+        675\n\
+        1456",
+    params: &[],
+    answers: &[Some("514579"), Some("241861950")],
+}];
+
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    multiply_entries(input, params, 2)
+}
+
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    multiply_entries(input, params, 3)
+}
+
+/// Multiplies the entries that sum to the target. The `target` and the number of entries `k`
+/// can be changed with parameters to query other reports.
+fn multiply_entries(input: &str, params: &Params, entries: usize) -> Result<Answer, Error> {
+    let expense_report = parse_expense_report(input)?;
+    let target = params.get(DAY, "target", 2020)?;
+    let k = params.get(DAY, "k", entries)?;
+    if k == 0 {
+        return Err(Error::invalid_parameter(DAY, "k", "0"));
+    }
+
+    let entries = find_sum(&expense_report, target, k)
+        .ok_or_else(|| Error::no_answer(DAY, format!("no {} entries sum to {}", k, target)))?;
+    Ok(Answer::new(
+        format!(
+            "The {} entries that sum to {} multiplied are {}",
+            k,
+            target,
+            entries.iter().join(" * ")
+        ),
+        subset_sum::product(&entries),
+    ))
+}
+
+pub fn parse_expense_report(input: &str) -> Result<Vec<u32>, Error> {
     // sort the expense report to make finding two sums easier
-    let mut expense_report = parse_to_ints(INPUT);
+    let mut expense_report = parse_to_ints(input)?;
     expense_report.sort();
+    Ok(expense_report)
+}
 
-    let pair = find_sum(&expense_report, 2020, 2);
-    println!(
-        "The two entries that sum to 2020 multiplied are {} * {} = {}",
-        pair[0],
-        pair[1],
-        pair.iter().product::<u32>()
-    );
-
-    let triplet = find_sum(&expense_report, 2020, 3);
-    println!(
-        "The three entries that sum to 2020 multiplied are {} * {} * {} = {}",
-        triplet[0],
-        triplet[1],
-        triplet[2],
-        triplet.iter().product::<u32>()
-    );
+/// Finds `k` entries of the sorted expense report that sum to `target`, in ascending order.
+///
+/// A pair is found in a single pass with `find_pair`. For more entries the first ones are picked
+/// in turn and the last two are found with two pointers over the rest of the report, which takes
+/// O(n^(k-1)) time instead of trying all O(n^k) combinations.
+pub fn find_sum(expense_report: &[u32], target: u32, k: usize) -> Option<Vec<u32>> {
+    if k == 2 {
+        return find_pair(expense_report, target).map(|(a, b)| vec![a, b]);
+    }
+    for_each_sum(expense_report, target, k, &mut vec![], &mut |entries| {
+        ControlFlow::Break(entries.to_vec())
+    })
+    .break_value()
+}
+
+/// Finds every distinct combination of `k` entries of the sorted expense report that sum to
+/// `target`, each in ascending order. An entry that is in the report more than once can be used
+/// as often as it is in there.
+pub fn find_all_sums(expense_report: &[u32], target: u32, k: usize) -> Vec<Vec<u32>> {
+    let mut found = vec![];
+    // collecting the sums never stops early
+    let _ = for_each_sum(expense_report, target, k, &mut vec![], &mut |entries| {
+        found.push(entries.to_vec());
+        ControlFlow::<()>::Continue(())
+    });
+    found
+}
+
+/// Finds two entries that sum to `target` by remembering the entries seen so far, the report
+/// doesn't have to be sorted for this.
+pub fn find_pair(entries: &[u32], target: u32) -> Option<(u32, u32)> {
+    let mut seen = HashSet::new();
+    for &entry in entries {
+        if let Some(complement) = target.checked_sub(entry) {
+            if seen.contains(&complement) {
+                return Some((complement.min(entry), complement.max(entry)));
+            }
+        }
+        seen.insert(entry);
+    }
+    None
 }
 
-fn find_sum(expense_report: &Vec<u32>, target: u32, numbers: usize) -> Vec<u32> {
-    expense_report
-        .iter()
-        .combinations(numbers)
-        .map(|vec| vec.iter().map(|&&i| i).collect::<Vec<u32>>())
-        .find(|vec| vec.iter().sum::<u32>() == target)
-        .expect("No sum found")
+/// Hands every distinct combination of `k` sorted entries that sum to `target` to `on_sum`,
+/// after the entries already picked in `picked`, until it breaks.
+fn for_each_sum<B>(
+    entries: &[u32],
+    target: u32,
+    k: usize,
+    picked: &mut Vec<u32>,
+    on_sum: &mut dyn FnMut(&[u32]) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    match k {
+        0 => {
+            if target == 0 {
+                on_sum(picked)?;
+            }
+        }
+        1 => {
+            if entries.binary_search(&target).is_ok() {
+                picked.push(target);
+                let flow = on_sum(picked);
+                picked.pop();
+                flow?;
+            }
+        }
+        2 => {
+            // move the smallest entry up or the largest entry down until they meet
+            let (mut low, mut high) = (0, entries.len().saturating_sub(1));
+            while low < high {
+                // the sum of two entries can be larger than a u32
+                let sum = u64::from(entries[low]) + u64::from(entries[high]);
+                match sum.cmp(&u64::from(target)) {
+                    Ordering::Less => low += 1,
+                    Ordering::Greater => high -= 1,
+                    Ordering::Equal => {
+                        picked.extend([entries[low], entries[high]]);
+                        let flow = on_sum(picked);
+                        picked.truncate(picked.len() - 2);
+                        flow?;
+                        // skip the entries that would give the same pair again
+                        let (first, last) = (entries[low], entries[high]);
+                        while low < high && entries[low] == first {
+                            low += 1;
+                        }
+                        while low < high && entries[high] == last {
+                            high -= 1;
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            for (index, &first) in entries.iter().enumerate() {
+                if index > 0 && entries[index - 1] == first {
+                    continue;
+                }
+                // the entries are sorted, so the rest is too large as well
+                if u64::from(first) * k as u64 > u64::from(target) {
+                    break;
+                }
+                picked.push(first);
+                let flow =
+                    for_each_sum(&entries[index + 1..], target - first, k - 1, picked, on_sum);
+                picked.pop();
+                flow?;
+            }
+        }
+    }
+    ControlFlow::Continue(())
 }
 
-fn parse_to_ints(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .map(|line| line.parse())
-        .filter_map(Result::ok)
-        .collect()
+pub fn parse_to_ints(input: &str) -> Result<Vec<u32>, Error> {
+    parse_lines(DAY, input, |line| {
+        line.parse()
+            .map_err(|_| "expected an expense as a positive number".to_string())
+    })
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_to_ints() {
         let input = "123\n456\n789";
         let expected = vec![123, 456, 789];
 
-        assert_eq!(parse_to_ints(input), expected);
+        assert_eq!(parse_to_ints(input), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_to_ints_invalid() {
+        let input = "123\n45x\n789";
+        let expected =
+            Error::invalid_line(DAY, 2, "45x", "expected an expense as a positive number");
+
+        assert_eq!(parse_to_ints(input), Err(expected));
     }
 
     #[test]
@@ -110,7 +267,7 @@ mod tests {
         expense_report.sort();
         let target = 2020;
 
-        assert_eq!(find_sum(&expense_report, target, 2), vec![299, 1721]);
+        assert_eq!(find_sum(&expense_report, target, 2), Some(vec![299, 1721]));
     }
 
     #[test]
@@ -119,6 +276,74 @@ mod tests {
         expense_report.sort();
         let target = 2020;
 
-        assert_eq!(find_sum(&expense_report, target, 3), vec![366, 675, 979]);
+        assert_eq!(
+            find_sum(&expense_report, target, 3),
+            Some(vec![366, 675, 979])
+        );
+    }
+
+    #[test]
+    fn test_find_sum_with_4() {
+        let expense_report = vec![299, 366, 675, 979, 1456, 1721];
+
+        assert_eq!(
+            find_sum(&expense_report, 2319, 4),
+            Some(vec![299, 366, 675, 979])
+        );
+        assert_eq!(find_sum(&expense_report, 2020, 4), None);
+        assert_eq!(find_sum(&expense_report, 979, 1), Some(vec![979]));
+    }
+
+    #[test]
+    fn test_find_pair() {
+        assert_eq!(find_pair(&[1721, 979, 366, 299], 2020), Some((299, 1721)));
+        assert_eq!(find_pair(&[1010, 1721], 2020), None);
+        assert_eq!(find_pair(&[1010, 1721, 1010], 2020), Some((1010, 1010)));
+    }
+
+    #[test]
+    fn test_find_all_sums() {
+        let entries = vec![1, 1, 2, 3, 4];
+
+        assert_eq!(find_all_sums(&entries, 5, 2), vec![vec![1, 4], vec![2, 3]]);
+        assert_eq!(
+            find_all_sums(&entries, 6, 3),
+            vec![vec![1, 1, 4], vec![1, 2, 3]]
+        );
+        assert_eq!(find_all_sums(&entries, 20, 3), Vec::<Vec<u32>>::new());
+    }
+
+    #[test]
+    fn test_find_all_sums_large_entries() {
+        let entries = vec![1, 2, u32::MAX - 1, u32::MAX];
+
+        assert_eq!(
+            find_all_sums(&entries, u32::MAX, 2),
+            vec![vec![1, u32::MAX - 1]]
+        );
+        assert_eq!(find_sum(&entries, u32::MAX, 3), None);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let input = EXAMPLES[0].input;
+        let mut params = Params::new();
+        params.set("target", "1345");
+        params.set("k", "2");
+
+        assert_eq!(
+            part_2(input, &params).map(|answer| answer.value.to_string()),
+            Ok((366 * 979).to_string())
+        );
+        params.set("target", "7");
+        assert_eq!(
+            part_1(input, &params),
+            Err(Error::no_answer(DAY, "no 2 entries sum to 7"))
+        );
+        params.set("k", "0");
+        assert_eq!(
+            part_1(input, &params),
+            Err(Error::invalid_parameter(DAY, "k", "0"))
+        );
     }
 }
@@ -0,0 +1,198 @@
+//! Subsets of any size of the expense report that sum to a target.
+//!
+//! Entries are told apart by their position in the report, an entry that is in there twice can
+//! be in a subset once or twice.
+use num::{BigUint, One, Zero};
+
+/// The most sums the tables of `count_subsets` and `subsets` keep, a larger table is an error
+/// instead of running out of memory
+pub const MAX_TABLE_SIZE: usize = 1 << 26;
+
+/// Counts the subsets of `entries` that sum to `target`.
+///
+/// Keeps the number of subsets for every sum up to the target while adding the entries one by
+/// one, which takes O(n * target) time and O(target) memory. The count can grow to 2^n, so it is
+/// kept as a big integer. A target above `MAX_TABLE_SIZE` is an error.
+pub fn count_subsets(entries: &[u32], target: u32) -> Result<BigUint, String> {
+    check_table_size(1, target)?;
+    let target = target as usize;
+    let mut counts = vec![BigUint::zero(); target + 1];
+    counts[0] = BigUint::one();
+    for &entry in entries {
+        let entry = entry as usize;
+        if entry > target {
+            continue;
+        }
+        // go from high to low sums so the entry is only added once to every subset
+        for sum in (entry..=target).rev() {
+            if !counts[sum - entry].is_zero() {
+                let with_entry = counts[sum - entry].clone();
+                counts[sum] += with_entry;
+            }
+        }
+    }
+    Ok(counts.swap_remove(target))
+}
+
+/// Lists the subsets of `entries` that sum to `target`, with their entries in the order of the
+/// report. The subsets are found one at a time, so the first ones are there long before all of
+/// them are counted.
+///
+/// Which sums can still be reached is known up front, for every entry and every sum up to the
+/// target. That table takes O(n * target) memory, more than `MAX_TABLE_SIZE` is an error.
+pub fn subsets(entries: &[u32], target: u32) -> Result<Subsets<'_>, String> {
+    check_table_size(entries.len() + 1, target)?;
+    let reachable = reachable_sums(entries, target);
+    let stack = if reachable[0][target as usize] {
+        vec![(0, target, vec![])]
+    } else {
+        vec![]
+    };
+    Ok(Subsets {
+        entries,
+        reachable,
+        stack,
+    })
+}
+
+/// The iterator of `subsets`
+pub struct Subsets<'a> {
+    entries: &'a [u32],
+    /// Whether the entries from an index on have a subset with a sum, for all sums up to the
+    /// target. Only the choices that can still reach the target are followed.
+    reachable: Vec<Vec<bool>>,
+    /// The choices still to follow: the index of the next entry, what is left of the target and
+    /// the entries picked so far
+    stack: Vec<(usize, u32, Vec<u32>)>,
+}
+
+impl Iterator for Subsets<'_> {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Vec<u32>> {
+        while let Some((index, remaining, picked)) = self.stack.pop() {
+            let entry = match self.entries.get(index) {
+                Some(&entry) => entry,
+                // every choice on the stack can reach the target, at the end it has
+                None => return Some(picked),
+            };
+            if self.reachable[index + 1][remaining as usize] {
+                self.stack.push((index + 1, remaining, picked.clone()));
+            }
+            if entry <= remaining && self.reachable[index + 1][(remaining - entry) as usize] {
+                let mut picked = picked;
+                picked.push(entry);
+                self.stack.push((index + 1, remaining - entry, picked));
+            }
+        }
+        None
+    }
+}
+
+/// Checks that a table of `rows` with a column for every sum up to `target` isn't too large
+fn check_table_size(rows: usize, target: u32) -> Result<(), String> {
+    match rows.checked_mul(target as usize + 1) {
+        Some(size) if size <= MAX_TABLE_SIZE => Ok(()),
+        _ => Err(format!(
+            "A table of {} rows of sums up to {} is larger than the {} sums it may have",
+            rows, target, MAX_TABLE_SIZE
+        )),
+    }
+}
+
+/// For every index, which sums up to `target` a subset of the entries from that index on has
+fn reachable_sums(entries: &[u32], target: u32) -> Vec<Vec<bool>> {
+    let target = target as usize;
+    let mut reachable = vec![vec![false; target + 1]; entries.len() + 1];
+    // nothing but the empty subset is left after the last entry
+    reachable[entries.len()][0] = true;
+    for (index, &entry) in entries.iter().enumerate().rev() {
+        for sum in 0..=target {
+            reachable[index][sum] = reachable[index + 1][sum]
+                || (entry as usize <= sum && reachable[index + 1][sum - entry as usize]);
+        }
+    }
+    reachable
+}
+
+/// Multiplies the entries without overflowing, however large or many they are
+pub fn product(entries: &[u32]) -> BigUint {
+    entries.iter().map(|&entry| BigUint::from(entry)).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_subsets() {
+        let entries = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(count_subsets(&entries, 2020), Ok(BigUint::from(2_u32)));
+        assert_eq!(count_subsets(&entries, 0), Ok(BigUint::one()));
+        assert_eq!(count_subsets(&entries, 1), Ok(BigUint::zero()));
+        assert_eq!(count_subsets(&[1, 1, 2], 2), Ok(BigUint::from(2_u32)));
+    }
+
+    #[test]
+    fn test_count_subsets_large() {
+        // every subset of 200 zeros sums to 0
+        let entries = vec![0; 200];
+
+        assert_eq!(count_subsets(&entries, 0), Ok(BigUint::one() << 200));
+    }
+
+    #[test]
+    fn test_subsets() {
+        let entries = vec![1721, 979, 366, 299, 675, 1456];
+        let mut found: Vec<_> = subsets(&entries, 2020).unwrap().collect();
+        found.sort();
+
+        assert_eq!(found, vec![vec![979, 366, 675], vec![1721, 299]]);
+        assert_eq!(subsets(&entries, 1).unwrap().next(), None);
+    }
+
+    #[test]
+    fn test_subsets_match_count() {
+        let entries = vec![1, 1, 2, 3, 4, 5, 0];
+
+        for target in 0..=17 {
+            assert_eq!(
+                Ok(BigUint::from(subsets(&entries, target).unwrap().count())),
+                count_subsets(&entries, target),
+                "target {}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_subsets_are_lazy() {
+        // listing all subsets of 64 zeros would never finish
+        let entries = vec![0; 64];
+
+        assert_eq!(subsets(&entries, 0).unwrap().take(3).count(), 3);
+    }
+
+    #[test]
+    fn test_table_too_large() {
+        let entries = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert!(count_subsets(&entries, u32::MAX).is_err());
+        assert!(subsets(&entries, u32::MAX).is_err());
+        // the table of subsets has a row for every entry
+        let target = (MAX_TABLE_SIZE / 4) as u32;
+        assert_eq!(check_table_size(1, target), Ok(()));
+        assert!(subsets(&entries, target).is_err());
+    }
+
+    #[test]
+    fn test_product() {
+        assert_eq!(product(&[299, 1721]), BigUint::from(514579_u32));
+        assert_eq!(
+            product(&[u32::MAX, u32::MAX, u32::MAX]).to_string(),
+            "79228162458924105385300197375"
+        );
+        assert_eq!(product(&[]), BigUint::one());
+    }
+}
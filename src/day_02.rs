@@ -57,109 +57,191 @@
 ///
 /// How many passwords are valid according to the new interpretation of the
 /// policies?
+use crate::answer::Answer;
+use crate::error::{numbered_lines, parse_lines_lenient, Error};
+use crate::example::Example;
+use crate::params::Params;
+use policy::{CountRange, OnePosition, PasswordPolicy, PolicyRegistry};
 use regex::Regex;
 
-const INPUT: &str = include_str!("../input/day_02.txt");
+pub mod policy;
 
-pub fn run() {
-    let passwords = parse_passwords(INPUT);
+const DAY: u8 = 2;
 
-    // validate passwords
-    let valid_passwords = find_valid_passwords(passwords.clone());
-    println!(
-        "The amount of passwords valid according to their policies is: {}",
-        valid_passwords.len()
-    );
+/// The policy the passwords are checked with
+pub const PARAMS: &[&str] = &["policy"];
 
-    let valid_toboggan_passwords = find_valid_toboggan_passwords(passwords);
-    println!(
-        "The amount of passwords valid according to the Toboggan policy is: {}",
-        valid_toboggan_passwords.len()
-    );
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        1-3 a: abcde\n\
+        1-3 b: cdefg\n\
+        2-9 c: ccccccccc",
+    params: &[],
+    answers: &[Some("2"), Some("1")],
+}];
+
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    count_valid_passwords(
+        input,
+        params,
+        "count",
+        "The amount of passwords valid according to their policies is",
+    )
+}
+
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    count_valid_passwords(
+        input,
+        params,
+        "positions",
+        "The amount of passwords valid according to the Toboggan policy is",
+    )
+}
+
+/// Counts the passwords that follow the policy of the part, or the policy given by the `policy`
+/// parameter
+fn count_valid_passwords(
+    input: &str,
+    params: &Params,
+    policy: &str,
+    label: &str,
+) -> Result<Answer, Error> {
+    let passwords = parse_passwords(input)?;
+    let spec = params.get(DAY, "policy", policy.to_string())?;
+    let label = if spec == policy {
+        label.to_string()
+    } else {
+        format!(
+            "The amount of passwords valid according to the {} policy is",
+            spec
+        )
+    };
+    let policy = PolicyRegistry::default()
+        .create(&spec)
+        .map_err(|reason| Error::invalid_parameter_because(DAY, "policy", &spec, reason))?;
+
+    let valid_passwords = passwords
+        .iter()
+        .filter(|password| policy.is_valid(password))
+        .count();
+    Ok(Answer::new(label, valid_passwords))
 }
-fn find_valid_passwords(passwords: Vec<Password>) -> Vec<Password> {
+
+pub fn find_valid_passwords(passwords: Vec<Password>) -> Vec<Password> {
     passwords
         .into_iter()
-        .filter(|password| {
-            let char_count = password
-                .password
-                .chars()
-                .filter(|&c| c == password.policy.char)
-                .count();
-            char_count >= password.policy.min && char_count <= password.policy.max
-        })
+        .filter(|password| CountRange.is_valid(password))
         .collect()
 }
 
-fn find_valid_toboggan_passwords(passwords: Vec<Password>) -> Vec<Password> {
+pub fn find_valid_toboggan_passwords(passwords: Vec<Password>) -> Vec<Password> {
     passwords
         .into_iter()
-        .filter(|password| {
-            password
-                .password
-                .chars()
-                .enumerate()
-                .filter(|&(i, _)| i + 1 == password.policy.min || i + 1 == password.policy.max)
-                .filter(|&(_, c)| c == password.policy.char)
-                .count()
-                == 1
-        })
+        .filter(|password| OnePosition.is_valid(password))
         .collect()
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Password {
-    policy: Policy,
-    password: String,
+pub struct Password {
+    pub policy: Policy,
+    pub password: String,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Policy {
-    min: usize,
-    max: usize,
-    char: char,
+pub struct Policy {
+    pub min: usize,
+    pub max: usize,
+    pub char: char,
+}
+
+/// Parses every line strictly, the first line that isn't a policy and a password is an error
+/// with its line number and text.
+pub fn parse_passwords(input: &str) -> Result<Vec<Password>, Error> {
+    Ok(parse_numbered_passwords(input)?
+        .into_iter()
+        .map(|(_, password)| password)
+        .collect())
 }
 
-fn parse_passwords(input: &str) -> Vec<Password> {
-    input
-        .lines()
-        .filter_map(|line| convert_to_password(line))
+/// Parses the passwords together with their line number, starting at 1, to point at them when
+/// explaining why they are invalid
+pub fn parse_numbered_passwords(input: &str) -> Result<Vec<(usize, Password)>, Error> {
+    numbered_lines(input)
+        .map(|(number, line)| {
+            convert_to_password(line)
+                .map(|password| (number, password))
+                .map_err(|reason| Error::invalid_line(DAY, number, line, reason))
+        })
         .collect()
 }
 
-fn convert_to_password(line: &str) -> Option<Password> {
+/// Parses the lines that are a policy and a password, with their line number, and skips the
+/// others. The skipped lines are returned as errors, the caller decides whether the passwords
+/// are still good enough without them.
+pub fn parse_passwords_lenient(input: &str) -> (Vec<(usize, Password)>, Vec<Error>) {
+    parse_lines_lenient(DAY, input, convert_to_password)
+}
+
+pub fn convert_to_password(line: &str) -> Result<Password, String> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"([0-9]+)-([0-9]+) (.): (.*)").unwrap();
+        static ref RE: Regex = Regex::new(r"^([0-9]+)-([0-9]+) (.): (.*)$").unwrap();
     }
-    let captures = RE.captures(line);
-    match captures {
-        Some(groups) => match (groups.get(1), groups.get(2), groups.get(3), groups.get(4)) {
-            (Some(min), Some(max), Some(char), Some(password)) => match (
-                min.as_str().parse(),
-                max.as_str().parse(),
-                char.as_str().chars().next(),
-                password.as_str().to_string(),
-            ) {
-                (Ok(min), Ok(max), Some(char), password) => Some(Password {
-                    password,
-                    policy: Policy { min, max, char },
-                }),
-
-                _ => None,
-            },
-            _ => None,
-        },
-        _ => None,
+    let groups = RE
+        .captures(line)
+        .ok_or_else(|| "expected a policy and a password like 1-3 a: abcde".to_string())?;
+    match (
+        groups[1].parse(),
+        groups[2].parse(),
+        groups[3].chars().next(),
+    ) {
+        (Ok(min), Ok(max), Some(char)) => Ok(Password {
+            password: groups[4].to_string(),
+            policy: Policy { min, max, char },
+        }),
+        _ => Err("the numbers of the policy are too large".to_string()),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
+
+    #[test]
+    fn test_policy_parameter() {
+        let mut params = Params::new();
+        params.set("policy", "exactly=1");
+
+        assert_eq!(
+            part_2(EXAMPLES[0].input, &params).map(|answer| answer.value.to_string()),
+            Ok("1".to_string())
+        );
+        params.set("policy", "length=1");
+        assert_eq!(
+            part_1(EXAMPLES[0].input, &params),
+            Err(Error::invalid_parameter_because(
+                DAY,
+                "policy",
+                "length=1",
+                "Unknown password policy length, the policies are: \
+                 any-of, count, exactly, matches, positions"
+            ))
+        );
+        params.set("policy", "matches=(");
+        assert!(part_1(EXAMPLES[0].input, &params)
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid regex for the matches policy"));
+    }
 
     #[test]
     fn test_parse_policies() {
-        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+        let input = EXAMPLES[0].input;
         let passwords = vec![
             Password {
                 password: "abcde".to_string(),
@@ -187,7 +269,36 @@ mod tests {
             },
         ];
 
-        assert_eq!(parse_passwords(input), passwords);
+        assert_eq!(parse_passwords(input), Ok(passwords));
+    }
+
+    #[test]
+    fn test_parse_passwords_strict_and_lenient() {
+        let input = "1-3 a: abcde\n1-3 b cdefg\n2-9 c: ccccccccc";
+        let reason = "expected a policy and a password like 1-3 a: abcde";
+
+        assert_eq!(
+            parse_passwords(input),
+            Err(Error::invalid_line(DAY, 2, "1-3 b cdefg", reason))
+        );
+        let (passwords, skipped) = parse_passwords_lenient(input);
+        assert_eq!(
+            passwords
+                .iter()
+                .map(|(line, password)| (*line, password.password.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "abcde"), (3, "ccccccccc")]
+        );
+        assert_eq!(
+            skipped,
+            vec![Error::invalid_line(DAY, 2, "1-3 b cdefg", reason)]
+        );
+    }
+
+    #[test]
+    fn test_convert_to_password_invalid() {
+        assert!(convert_to_password("1-3 a abcde").is_err());
+        assert!(convert_to_password("1-99999999999999999999 a: abcde").is_err());
     }
 
     #[test]
@@ -0,0 +1,594 @@
+//! The ways to read the policy in front of a password.
+//!
+//! Every line of the database has a policy like `1-3 a`, what it means depends on who is
+//! asked. The sled rental place counts the letter, the Toboggan Corporate Policy looks at two
+//! positions. Other readings can be added to a `PolicyRegistry` and selected by name.
+//!
+//! Every policy explains its verdict with what it observed in the password, `to_csv` exports
+//! these for all passwords.
+use super::{Password, Policy};
+use crate::answer::csv_field;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Decides whether a password follows its policy.
+pub trait PasswordPolicy {
+    /// The name the policy is selected by, with its value if it has one
+    fn name(&self) -> String;
+
+    /// Looks at the password and gives the reason when it doesn't follow the policy
+    fn check(&self, password: &Password) -> Verdict;
+
+    fn is_valid(&self, password: &Password) -> bool {
+        self.check(password).is_valid()
+    }
+}
+
+/// The letter appears at least `min` and at most `max` times, the policy of part 1
+pub struct CountRange;
+
+impl PasswordPolicy for CountRange {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+
+    fn check(&self, password: &Password) -> Verdict {
+        let Policy { min, max, char } = password.policy;
+        let count = count_char(password);
+        Verdict::new(
+            self,
+            Observation::Count(count),
+            (count < min || count > max).then(|| {
+                format!(
+                    "{} appears {} times, expected {} to {} times",
+                    char, count, min, max
+                )
+            }),
+        )
+    }
+}
+
+/// The letter is at exactly one of the positions `min` and `max`, counting from 1, the
+/// Toboggan policy of part 2
+pub struct OnePosition;
+
+impl PasswordPolicy for OnePosition {
+    fn name(&self) -> String {
+        "positions".to_string()
+    }
+
+    fn check(&self, password: &Password) -> Verdict {
+        let Policy { min, max, char } = password.policy;
+        let at = |position: usize| {
+            position
+                .checked_sub(1)
+                .and_then(|index| password.password.chars().nth(index))
+        };
+        let found = (at(min), at(max));
+        let reason = match (found.0 == Some(char), found.1 == Some(char)) {
+            (true, true) if min != max => {
+                Some(format!("{} is at both position {} and {}", char, min, max))
+            }
+            (false, false) => Some(format!(
+                "{} is at neither position {} nor {}, found {}",
+                char,
+                min,
+                max,
+                Observation::Positions(found.0, found.1)
+            )),
+            _ => None,
+        };
+        Verdict::new(self, Observation::Positions(found.0, found.1), reason)
+    }
+}
+
+/// The letter appears exactly this many times, whatever the numbers of the policy are
+pub struct ExactCount(pub usize);
+
+impl PasswordPolicy for ExactCount {
+    fn name(&self) -> String {
+        format!("exactly={}", self.0)
+    }
+
+    fn check(&self, password: &Password) -> Verdict {
+        let count = count_char(password);
+        Verdict::new(
+            self,
+            Observation::Count(count),
+            (count != self.0).then(|| {
+                format!(
+                    "{} appears {} times, expected exactly {} times",
+                    password.policy.char, count, self.0
+                )
+            }),
+        )
+    }
+}
+
+/// At least one of these characters is in the password
+pub struct AnyOf(pub Vec<char>);
+
+impl PasswordPolicy for AnyOf {
+    fn name(&self) -> String {
+        format!("any-of={}", self.0.iter().collect::<String>())
+    }
+
+    fn check(&self, password: &Password) -> Verdict {
+        let found: Vec<char> = self
+            .0
+            .iter()
+            .copied()
+            .filter(|&c| password.password.contains(c))
+            .collect();
+        let reason = found
+            .is_empty()
+            .then(|| format!("contains none of {}", self.0.iter().collect::<String>()));
+        Verdict::new(self, Observation::Found(found), reason)
+    }
+}
+
+/// The password matches a regular expression
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches {
+    fn name(&self) -> String {
+        format!("matches={}", self.0)
+    }
+
+    fn check(&self, password: &Password) -> Verdict {
+        let matched = self
+            .0
+            .find(&password.password)
+            .map(|found| found.as_str().to_string());
+        let reason = matched
+            .is_none()
+            .then(|| format!("doesn't match {}", self.0));
+        Verdict::new(self, Observation::Match(matched), reason)
+    }
+}
+
+fn count_char(password: &Password) -> usize {
+    password
+        .password
+        .chars()
+        .filter(|&c| c == password.policy.char)
+        .count()
+}
+
+/// Creates a policy from the value after the `=` in its name, or `None` without one
+pub type Constructor = fn(Option<&str>) -> Result<Box<dyn PasswordPolicy>, String>;
+
+/// The policies that can be selected by name, see `create`. The default registry has all the
+/// policies of this module.
+pub struct PolicyRegistry {
+    constructors: HashMap<String, Constructor>,
+}
+
+impl PolicyRegistry {
+    pub fn empty() -> PolicyRegistry {
+        PolicyRegistry {
+            constructors: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, name: &str, constructor: Constructor) {
+        self.constructors.insert(name.to_string(), constructor);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.constructors.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Creates a policy by its name, a policy that needs a value gets it after an `=`, like
+    /// `exactly=2`
+    pub fn create(&self, spec: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+        let (name, value) = match spec.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (spec, None),
+        };
+        let constructor = self.constructors.get(name).ok_or_else(|| {
+            format!(
+                "Unknown password policy {}, the policies are: {}",
+                name,
+                self.names().join(", ")
+            )
+        })?;
+        constructor(value)
+    }
+}
+
+impl Default for PolicyRegistry {
+    fn default() -> PolicyRegistry {
+        let mut registry = PolicyRegistry::empty();
+        registry.register("count", |value| without_value("count", value, CountRange));
+        registry.register("positions", |value| {
+            without_value("positions", value, OnePosition)
+        });
+        registry.register("exactly", |value| {
+            let count = value
+                .and_then(|count| count.parse().ok())
+                .ok_or("The exactly policy needs a count, like exactly=2")?;
+            Ok(Box::new(ExactCount(count)))
+        });
+        registry.register("any-of", |value| match value {
+            Some(chars) if !chars.is_empty() => Ok(Box::new(AnyOf(chars.chars().collect()))),
+            _ => Err("The any-of policy needs characters, like any-of=xyz".to_string()),
+        });
+        registry.register("matches", |value| {
+            let pattern = value.ok_or("The matches policy needs a regex, like matches=^a")?;
+            Regex::new(pattern)
+                .map(|regex| Box::new(Matches(regex)) as Box<dyn PasswordPolicy>)
+                .map_err(|error| format!("Invalid regex for the matches policy: {}", error))
+        });
+        registry
+    }
+}
+
+fn without_value(
+    name: &str,
+    value: Option<&str>,
+    policy: impl PasswordPolicy + 'static,
+) -> Result<Box<dyn PasswordPolicy>, String> {
+    match value {
+        None => Ok(Box::new(policy)),
+        Some(value) => Err(format!(
+            "The {} policy doesn't take a value, got {}",
+            name, value
+        )),
+    }
+}
+
+/// What a policy looked at in a password
+#[derive(Debug, PartialEq, Clone)]
+pub enum Observation {
+    /// How many times the letter of the policy appears
+    Count(usize),
+    /// The characters at the positions `min` and `max`, `None` past the end of the password
+    Positions(Option<char>, Option<char>),
+    /// Which of the characters are in the password
+    Found(Vec<char>),
+    /// The part of the password that matched, if any
+    Match(Option<String>),
+}
+
+impl fmt::Display for Observation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let char_at = |found: &Option<char>| match found {
+            Some(c) => c.to_string(),
+            None => "nothing".to_string(),
+        };
+        match self {
+            Observation::Count(count) => write!(f, "{} times", count),
+            Observation::Positions(first, second) => {
+                write!(f, "{} and {}", char_at(first), char_at(second))
+            }
+            Observation::Found(found) if found.is_empty() => write!(f, "none"),
+            Observation::Found(found) => write!(f, "{}", found.iter().collect::<String>()),
+            Observation::Match(Some(matched)) => write!(f, "matched {}", matched),
+            Observation::Match(None) => write!(f, "no match"),
+        }
+    }
+}
+
+/// Whether a password follows one policy, and why
+#[derive(Debug, PartialEq, Clone)]
+pub struct Verdict {
+    pub policy: String,
+    pub observation: Observation,
+    /// Why the password doesn't follow the policy, `None` when it does
+    pub rejection: Option<String>,
+}
+
+impl Verdict {
+    pub fn new(
+        policy: &(impl PasswordPolicy + ?Sized),
+        observation: Observation,
+        rejection: Option<String>,
+    ) -> Verdict {
+        Verdict {
+            policy: policy.name(),
+            observation,
+            rejection,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.rejection.is_none()
+    }
+}
+
+/// The verdicts of every policy on the password of one line
+#[derive(Debug, PartialEq)]
+pub struct Report<'a> {
+    /// The line of the password in the input, starting at 1
+    pub line: usize,
+    pub password: &'a Password,
+    pub verdicts: Vec<Verdict>,
+}
+
+impl fmt::Display for Report<'_> {
+    /// Prints the line and the password, followed by the reason of every rejection
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Policy { min, max, char } = self.password.policy;
+        write!(
+            f,
+            "Line {}: {}-{} {}: {}",
+            self.line, min, max, char, self.password.password
+        )?;
+        for verdict in self.verdicts.iter() {
+            match &verdict.rejection {
+                Some(reason) => write!(f, "\n    {}: {}", verdict.policy, reason)?,
+                None => write!(f, "\n    {}: valid", verdict.policy)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the password of every line against every policy
+pub fn check_passwords<'a>(
+    passwords: &'a [(usize, Password)],
+    policies: &[Box<dyn PasswordPolicy>],
+) -> Vec<Report<'a>> {
+    passwords
+        .iter()
+        .map(|(line, password)| Report {
+            line: *line,
+            password,
+            verdicts: policies
+                .iter()
+                .map(|policy| policy.check(password))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Exports the verdicts as CSV, one row for every password and policy
+pub fn to_csv(reports: &[Report]) -> String {
+    let mut csv = "line,policy,password,verdict,observed,reason\n".to_string();
+    for report in reports {
+        let Policy { min, max, char } = report.password.policy;
+        for verdict in report.verdicts.iter() {
+            let fields = [
+                report.line.to_string(),
+                verdict.policy.clone(),
+                format!("{}-{} {}: {}", min, max, char, report.password.password),
+                if verdict.is_valid() {
+                    "valid"
+                } else {
+                    "invalid"
+                }
+                .to_string(),
+                verdict.observation.to_string(),
+                verdict.rejection.clone().unwrap_or_default(),
+            ];
+            let row: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::{parse_numbered_passwords, parse_passwords};
+
+    const INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n3-4 z: az";
+
+    fn passwords() -> Vec<Password> {
+        parse_passwords(INPUT).unwrap()
+    }
+
+    fn valid(policy: &dyn PasswordPolicy) -> Vec<bool> {
+        passwords()
+            .iter()
+            .map(|password| policy.is_valid(password))
+            .collect()
+    }
+
+    fn rejections(policy: &dyn PasswordPolicy) -> Vec<Option<String>> {
+        passwords()
+            .iter()
+            .map(|password| policy.check(password).rejection)
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_policies() {
+        assert_eq!(valid(&CountRange), vec![true, false, true, false]);
+        // position 3 and 4 are past the end of az
+        assert_eq!(valid(&OnePosition), vec![true, false, false, false]);
+        assert_eq!(valid(&ExactCount(1)), vec![true, false, false, true]);
+        assert_eq!(
+            valid(&AnyOf(vec!['x', 'g'])),
+            vec![false, true, false, false]
+        );
+        assert_eq!(
+            valid(&Matches(Regex::new("^c+$").unwrap())),
+            vec![false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_rejections() {
+        assert_eq!(
+            rejections(&CountRange),
+            vec![
+                None,
+                Some("b appears 0 times, expected 1 to 3 times".to_string()),
+                None,
+                Some("z appears 1 times, expected 3 to 4 times".to_string()),
+            ]
+        );
+        assert_eq!(
+            rejections(&OnePosition),
+            vec![
+                None,
+                Some("b is at neither position 1 nor 3, found c and e".to_string()),
+                Some("c is at both position 2 and 9".to_string()),
+                Some("z is at neither position 3 nor 4, found nothing and nothing".to_string()),
+            ]
+        );
+        assert_eq!(
+            rejections(&ExactCount(2))[2],
+            Some("c appears 9 times, expected exactly 2 times".to_string())
+        );
+        assert_eq!(
+            rejections(&AnyOf(vec!['x', 'y']))[0],
+            Some("contains none of xy".to_string())
+        );
+        assert_eq!(
+            rejections(&Matches(Regex::new("^c+$").unwrap()))[0],
+            Some("doesn't match ^c+$".to_string())
+        );
+    }
+
+    #[test]
+    fn test_observations() {
+        let password = &passwords()[0];
+
+        assert_eq!(
+            CountRange.check(password).observation,
+            Observation::Count(1)
+        );
+        assert_eq!(
+            OnePosition.check(password).observation,
+            Observation::Positions(Some('a'), Some('c'))
+        );
+        assert_eq!(
+            AnyOf(vec!['e', 'x', 'b']).check(password).observation,
+            Observation::Found(vec!['e', 'b'])
+        );
+        assert_eq!(
+            Matches(Regex::new("b.d").unwrap())
+                .check(password)
+                .observation,
+            Observation::Match(Some("bcd".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = PolicyRegistry::default();
+
+        assert_eq!(registry.create("exactly=2").unwrap().name(), "exactly=2");
+        assert_eq!(registry.create("any-of=xyz").unwrap().name(), "any-of=xyz");
+        assert_eq!(
+            registry.create("matches=^a=b").unwrap().name(),
+            "matches=^a=b"
+        );
+        assert!(registry.create("exactly").is_err());
+        assert!(registry.create("count=2").is_err());
+        assert!(registry.create("matches=(").is_err());
+        assert_eq!(
+            registry.create("length").err(),
+            Some(
+                "Unknown password policy length, the policies are: any-of, count, exactly, \
+                matches, positions"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        struct Long;
+        impl PasswordPolicy for Long {
+            fn name(&self) -> String {
+                "long".to_string()
+            }
+
+            fn check(&self, password: &Password) -> Verdict {
+                let length = password.password.len();
+                Verdict::new(
+                    self,
+                    Observation::Count(length),
+                    (length <= 8).then(|| format!("{} characters is too short", length)),
+                )
+            }
+        }
+
+        let mut registry = PolicyRegistry::empty();
+        registry.register("long", |_| Ok(Box::new(Long)));
+
+        assert_eq!(registry.names(), vec!["long"]);
+        assert_eq!(
+            valid(registry.create("long").unwrap().as_ref()),
+            vec![false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_check_passwords() {
+        let passwords = parse_numbered_passwords(INPUT).unwrap();
+        let policies: Vec<Box<dyn PasswordPolicy>> =
+            vec![Box::new(CountRange), Box::new(OnePosition)];
+        let reports = check_passwords(&passwords[..2], &policies);
+
+        assert_eq!(
+            reports,
+            vec![
+                Report {
+                    line: 1,
+                    password: &passwords[0].1,
+                    verdicts: vec![
+                        Verdict {
+                            policy: "count".to_string(),
+                            observation: Observation::Count(1),
+                            rejection: None,
+                        },
+                        Verdict {
+                            policy: "positions".to_string(),
+                            observation: Observation::Positions(Some('a'), Some('c')),
+                            rejection: None,
+                        },
+                    ],
+                },
+                Report {
+                    line: 2,
+                    password: &passwords[1].1,
+                    verdicts: vec![
+                        Verdict {
+                            policy: "count".to_string(),
+                            observation: Observation::Count(0),
+                            rejection: Some("b appears 0 times, expected 1 to 3 times".to_string()),
+                        },
+                        Verdict {
+                            policy: "positions".to_string(),
+                            observation: Observation::Positions(Some('c'), Some('e')),
+                            rejection: Some(
+                                "b is at neither position 1 nor 3, found c and e".to_string()
+                            ),
+                        },
+                    ],
+                },
+            ]
+        );
+        assert_eq!(
+            reports[1].to_string(),
+            "Line 2: 1-3 b: cdefg\n    \
+            count: b appears 0 times, expected 1 to 3 times\n    \
+            positions: b is at neither position 1 nor 3, found c and e"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let passwords = parse_numbered_passwords(INPUT).unwrap();
+        let policies: Vec<Box<dyn PasswordPolicy>> = vec![Box::new(CountRange)];
+
+        assert_eq!(
+            to_csv(&check_passwords(&passwords, &policies)),
+            "line,policy,password,verdict,observed,reason\n\
+            1,count,1-3 a: abcde,valid,1 times,\n\
+            2,count,1-3 b: cdefg,invalid,0 times,\"b appears 0 times, expected 1 to 3 times\"\n\
+            3,count,2-9 c: ccccccccc,valid,9 times,\n\
+            4,count,3-4 z: az,invalid,1 times,\"z appears 1 times, expected 3 to 4 times\"\n"
+        );
+    }
+}
@@ -89,128 +89,189 @@
 ///
 /// What do you get if you multiply together the number of trees encountered on
 /// each of the listed slopes?
-use std::collections::HashSet;
-use std::ops::AddAssign;
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::example::Example;
+use crate::grid::{Grid, Position, Wrap};
+use crate::params::Params;
+use std::ops::RangeInclusive;
 
-const INPUT: &str = include_str!("../input/day_03.txt");
+pub mod render;
 
-pub fn run() {
-    let map = parse_map(INPUT);
+const DAY: u8 = 3;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        ..##.......\n\
+        #...#...#..\n\
+        .#....#..#.\n\
+        ..#.#...#.#\n\
+        .#...##..#.\n\
+        ..#.##.....\n\
+        .#.#.#....#\n\
+        .#........#\n\
+        #.##...#...\n\
+        #...##....#\n\
+        .#..#...#.#",
+    params: &[],
+    answers: &[Some("7"), Some("336")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let map = parse_map(input)?;
 
     let trees_hit = traverse(&map, 3, 1);
-    println!(
-        "Following a slope of right 3 and down 1, the amount of trees hit is: {}",
-        trees_hit
-    );
+    Ok(Answer::new(
+        "Following a slope of right 3 and down 1, the amount of trees hit is",
+        trees_hit,
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let map = parse_map(input)?;
 
     let answer = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
         .iter()
         .map(|&(right, down)| traverse(&map, right, down))
         .map(|trees_hit| trees_hit as i64)
         .product::<i64>();
-    println!(
-        "Multiplying the number of trees encountered for all slops gives: {}",
-        answer
-    );
+    Ok(Answer::new(
+        "Multiplying the number of trees encountered for all slops gives",
+        answer,
+    ))
 }
 
-fn traverse(map: &Map, right: i32, down: i32) -> i32 {
+pub fn traverse(map: &Map, right: isize, down: isize) -> i32 {
     // traverse the map, reporting on how many trees were hit along the slope
-    // start at 0, 0
-    let mut location = Point { x: 0, y: 0 };
-
-    let mut trees_hit = 0;
-    while location.x < map.height as i32 {
-        // do this until the full map is traversed
-        location += Point { x: down, y: right };
-        location.wrap_horizontal(map.width as i32);
-        if map.trees.contains(&location) {
-            trees_hit += 1;
-        }
-    }
-    trees_hit
+    // the map repeats to the right
+    path(map, right, down, Wrap::Horizontal)
+        .iter()
+        .filter(|step| step.tree)
+        .count() as i32
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Map {
-    width: usize,
-    height: usize,
-    trees: Trees,
-}
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
+/// A square the toboggan passes on its way down
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Step {
+    pub location: Position,
+    pub tree: bool,
 }
-impl AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+
+/// Follows a slope from the top left, which can go left or up as well with negative steps, and
+/// gives every square passed after the start.
+///
+/// The path ends when it leaves the map over an edge that doesn't wrap. On a map that wraps in
+/// the direction of the slope the path would go on forever, it ends when it is back at the start.
+pub fn path(map: &Map, right: isize, down: isize, wrap: Wrap) -> Vec<Step> {
+    let start = (0, 0);
+    let mut location = start;
+    let mut steps = vec![];
+    while let Some(next) = map.offset(location, (down, right), wrap) {
+        if next == start {
+            break;
+        }
+        location = next;
+        steps.push(Step {
+            location,
+            tree: map[location] == Square::Tree,
+        });
     }
+    steps
 }
-impl Point {
-    fn wrap_horizontal(&mut self, max_y: i32) {
-        self.y %= max_y;
-    }
+
+/// A slope and the amount of trees hit following it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Slope {
+    pub right: isize,
+    pub down: isize,
+    pub trees_hit: usize,
 }
-type Trees = HashSet<Point>;
-
-fn parse_map(input: &str) -> Map {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().chars().count();
-    let trees = input
-        .lines()
-        .enumerate()
-        .flat_map(|(x, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|&(_, c)| c == '#')
-                .map(move |(y, _)| Point {
-                    x: x as i32,
-                    y: y as i32,
-                })
+
+/// Tries every slope within the bounds and gives the one that hits the fewest trees, the first
+/// one tried when several hit as few. Slopes that don't go down aren't a way down the hill, they
+/// are skipped.
+pub fn fewest_trees(
+    map: &Map,
+    rights: RangeInclusive<isize>,
+    downs: RangeInclusive<isize>,
+    wrap: Wrap,
+) -> Option<Slope> {
+    rights
+        .flat_map(|right| downs.clone().map(move |down| (right, down)))
+        .filter(|&(_, down)| down > 0)
+        .map(|(right, down)| Slope {
+            right,
+            down,
+            trees_hit: path(map, right, down, wrap)
+                .iter()
+                .filter(|step| step.tree)
+                .count(),
         })
-        .collect();
-    Map {
-        width,
-        height,
-        trees,
-    }
+        .min_by_key(|slope| slope.trees_hit)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Square {
+    Open,
+    Tree,
+}
+pub type Map = Grid<Square>;
+
+pub fn parse_map(input: &str) -> Result<Map, Error> {
+    Grid::parse(DAY, input, |c| match c {
+        '#' => Ok(Square::Tree),
+        '.' => Ok(Square::Open),
+        _ => Err(format!("unknown square {}, expected . or #", c)),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_map() {
-        // ..##.......
-        // #...#...#..
-        // .#....#..#.
-        // ..#.#...#.#
         let input = "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#";
+        let map = parse_map(input).unwrap();
 
-        let mut expected_trees = Trees::new();
-        expected_trees.insert(Point { x: 0, y: 2 });
-        expected_trees.insert(Point { x: 0, y: 3 });
-        expected_trees.insert(Point { x: 1, y: 0 });
-        expected_trees.insert(Point { x: 1, y: 4 });
-        expected_trees.insert(Point { x: 1, y: 8 });
-        expected_trees.insert(Point { x: 2, y: 1 });
-        expected_trees.insert(Point { x: 2, y: 6 });
-        expected_trees.insert(Point { x: 2, y: 9 });
-        expected_trees.insert(Point { x: 3, y: 2 });
-        expected_trees.insert(Point { x: 3, y: 4 });
-        expected_trees.insert(Point { x: 3, y: 8 });
-        expected_trees.insert(Point { x: 3, y: 10 });
-
-        let expected = Map {
-            width: 11,
-            height: 4,
-            trees: expected_trees,
-        };
-
-        assert_eq!(parse_map(input), expected);
+        assert_eq!((map.width(), map.height()), (11, 4));
+        assert_eq!(map[(0, 2)], Square::Tree);
+        assert_eq!(map[(0, 1)], Square::Open);
+        assert_eq!(
+            map.iter()
+                .filter(|(_, &square)| square == Square::Tree)
+                .map(|(location, _)| location)
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 2),
+                (0, 3),
+                (1, 0),
+                (1, 4),
+                (1, 8),
+                (2, 1),
+                (2, 6),
+                (2, 9),
+                (3, 2),
+                (3, 4),
+                (3, 8),
+                (3, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_map_invalid() {
+        let input = "..#\n#.#\n.o.\n";
+        let expected = Error::invalid_line(DAY, 3, ".o.", "unknown square o, expected . or #");
+
+        assert_eq!(parse_map(input), Err(expected));
+        assert!(parse_map("..#\n#.\n").is_err());
     }
 
     #[test]
@@ -226,23 +287,69 @@ mod tests {
         // #.##...#...
         // #...##....#
         // .#..#...#.#
-        let input = "\
-            ..##.......\n\
-            #...#...#..\n\
-            .#....#..#.\n\
-            ..#.#...#.#\n\
-            .#...##..#.\n\
-            ..#.##.....\n\
-            .#.#.#....#\n\
-            .#........#\n\
-            #.##...#...\n\
-            #...##....#\n\
-            .#..#...#.#";
+        let input = EXAMPLES[0].input;
         // cheat a bit by using parse_map to build the map
-        let map = parse_map(input);
+        let map = parse_map(input).unwrap();
 
         // traverse the trees with a slope of right 3, down 1 (the example from the description)
         // this should encounter 7 trees
         assert_eq!(traverse(&map, 3, 1), 7)
     }
+
+    #[test]
+    fn test_path() {
+        let map = parse_map(EXAMPLES[0].input).unwrap();
+
+        let steps = path(&map, 3, 1, Wrap::Horizontal);
+        assert_eq!(steps.len(), 10);
+        assert_eq!(
+            steps[..3],
+            [
+                Step {
+                    location: (1, 3),
+                    tree: false
+                },
+                Step {
+                    location: (2, 6),
+                    tree: true
+                },
+                Step {
+                    location: (3, 9),
+                    tree: false
+                },
+            ]
+        );
+        // the map wraps from column 12 back to 1
+        assert_eq!(steps[3].location, (4, 1));
+        assert_eq!(steps.iter().filter(|step| step.tree).count(), 7);
+    }
+
+    #[test]
+    fn test_path_wrapping() {
+        let map = parse_map(EXAMPLES[0].input).unwrap();
+
+        // without wrapping the toboggan leaves the map on the right
+        assert_eq!(path(&map, 3, 1, Wrap::None).len(), 3);
+        // going up wraps to the bottom
+        assert_eq!(path(&map, 0, -1, Wrap::Vertical)[0].location, (10, 0));
+        assert_eq!(path(&map, 1, -1, Wrap::Vertical).len(), 10);
+        // wrapping both ways it goes around until it is back at the start
+        assert_eq!(path(&map, 1, 1, Wrap::Both).len(), 10);
+        assert_eq!(path(&map, 0, 0, Wrap::Both), vec![]);
+    }
+
+    #[test]
+    fn test_fewest_trees() {
+        let map = parse_map(EXAMPLES[0].input).unwrap();
+
+        assert_eq!(
+            fewest_trees(&map, 1..=7, 1..=2, Wrap::Horizontal),
+            Some(Slope {
+                right: 5,
+                down: 2,
+                trees_hit: 0
+            })
+        );
+        assert_eq!(fewest_trees(&map, 1..=7, 0..=0, Wrap::Horizontal), None);
+    }
 }
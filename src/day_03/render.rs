@@ -0,0 +1,206 @@
+//! Draws the path of a slope on the map, to see where the toboggan goes.
+//!
+//! The map is repeated to the right as far as the path goes, or to the left for slopes going
+//! left. Like the illustration of the puzzle, the squares passed are marked with `O` when they
+//! are open and `X` when there is a tree. The drawing is text or an SVG image.
+use super::{path, Map, Square};
+use crate::grid::Wrap;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The size of a square in an SVG image, in pixels
+const SQUARE_SIZE: usize = 10;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Svg,
+}
+
+impl Format {
+    /// An SVG image for files ending in `.svg`, text for all others
+    pub fn from_path(file: &Path) -> Format {
+        match file.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("svg") => Format::Svg,
+            _ => Format::Text,
+        }
+    }
+}
+
+/// What is at a square of the drawing
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Mark {
+    Open,
+    Tree,
+    OpenPassed,
+    TreeHit,
+}
+
+impl Mark {
+    fn to_char(self) -> char {
+        match self {
+            Mark::Open => '.',
+            Mark::Tree => '#',
+            Mark::OpenPassed => 'O',
+            Mark::TreeHit => 'X',
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Mark::Open => "white",
+            Mark::Tree => "forestgreen",
+            Mark::OpenPassed => "skyblue",
+            Mark::TreeHit => "red",
+        }
+    }
+}
+
+/// Draws the path of the slope of right and down from the top left, in the given format
+pub fn render(map: &Map, right: isize, down: isize, format: Format) -> String {
+    let marks = marks(map, right, down);
+    match format {
+        Format::Text => marks
+            .iter()
+            .map(|row| row.iter().map(|mark| mark.to_char()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Format::Svg => to_svg(&marks),
+    }
+}
+
+/// Writes the drawing of a slope to a file, as an SVG image when the file ends in `.svg`
+pub fn write(map: &Map, right: isize, down: isize, file: &Path) -> io::Result<()> {
+    fs::write(file, render(map, right, down, Format::from_path(file)))
+}
+
+/// The marks of every square of the repeated map, row by row
+fn marks(map: &Map, right: isize, down: isize) -> Vec<Vec<Mark>> {
+    if map.width() == 0 {
+        // a map without columns has no path and nothing to draw
+        return Vec::new();
+    }
+    let width = map.width() as isize;
+    // the path wraps around, follow the columns of the repeated map instead
+    let passed: HashMap<(usize, isize), bool> = path(map, right, down, Wrap::Horizontal)
+        .iter()
+        .enumerate()
+        .map(|(index, step)| ((step.location.0, right * (index as isize + 1)), step.tree))
+        .collect();
+    let columns = passed.keys().map(|&(_, column)| column);
+    let first_tile = columns.clone().min().unwrap_or(0).min(0).div_euclid(width);
+    let last_tile = columns.max().unwrap_or(0).max(0).div_euclid(width);
+
+    (0..map.height())
+        .map(|row| {
+            (first_tile * width..(last_tile + 1) * width)
+                .map(|column| {
+                    let tree = map[(row, column.rem_euclid(width) as usize)] == Square::Tree;
+                    match (passed.contains_key(&(row, column)), tree) {
+                        (false, false) => Mark::Open,
+                        (false, true) => Mark::Tree,
+                        (true, false) => Mark::OpenPassed,
+                        (true, true) => Mark::TreeHit,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn to_svg(marks: &[Vec<Mark>]) -> String {
+    let height = marks.len() * SQUARE_SIZE;
+    let width = marks.first().map_or(0, |row| row.len()) * SQUARE_SIZE;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        width, height
+    );
+    svg.push_str(&format!(
+        "  <rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+        width,
+        height,
+        Mark::Open.color()
+    ));
+    for (row, marks) in marks.iter().enumerate() {
+        for (column, &mark) in marks.iter().enumerate() {
+            if mark != Mark::Open {
+                svg.push_str(&format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\" fill=\"{}\"/>\n",
+                    column * SQUARE_SIZE,
+                    row * SQUARE_SIZE,
+                    mark.color(),
+                    size = SQUARE_SIZE
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_03::{parse_map, EXAMPLES};
+
+    #[test]
+    fn test_render_text() {
+        let map = parse_map(EXAMPLES[0].input).unwrap();
+
+        // the illustration of the puzzle, as far as the path goes
+        assert_eq!(
+            render(&map, 3, 1, Format::Text),
+            "\
+            ..##.........##.........##.......\n\
+            #..O#...#..#...#...#..#...#...#..\n\
+            .#....X..#..#....#..#..#....#..#.\n\
+            ..#.#...#O#..#.#...#.#..#.#...#.#\n\
+            .#...##..#..X...##..#..#...##..#.\n\
+            ..#.##.......#.X#.......#.##.....\n\
+            .#.#.#....#.#.#.#.O..#.#.#.#....#\n\
+            .#........#.#........X.#........#\n\
+            #.##...#...#.##...#...#.X#...#...\n\
+            #...##....##...##....##...#X....#\n\
+            .#..#...#.#.#..#...#.#.#..#...X.#"
+        );
+    }
+
+    #[test]
+    fn test_render_left() {
+        let map = parse_map("..#\n#..\n.#.").unwrap();
+
+        assert_eq!(render(&map, -1, 1, Format::Text), "..#..#\n#.O#..\n.X..#.");
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let map = parse_map("..#\n#..").unwrap();
+        let svg = render(&map, 1, 1, Format::Svg);
+
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\">"));
+        assert!(svg
+            .contains("<rect x=\"20\" y=\"0\" width=\"10\" height=\"10\" fill=\"forestgreen\"/>"));
+        assert!(
+            svg.contains("<rect x=\"10\" y=\"10\" width=\"10\" height=\"10\" fill=\"skyblue\"/>")
+        );
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let map = parse_map("\n").unwrap();
+
+        assert_eq!(render(&map, 3, 1, Format::Text), "");
+        assert!(render(&map, 3, 1, Format::Svg).contains("width=\"0\" height=\"0\""));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("path.SVG")), Format::Svg);
+        assert_eq!(Format::from_path(Path::new("path.txt")), Format::Text);
+        assert_eq!(Format::from_path(Path::new("path")), Format::Text);
+    }
+}
@@ -138,39 +138,134 @@
 /// Count the number of valid passports - those that have all required fields
 /// and valid values. Continue to treat cid as optional. In your batch file, how
 /// many passports are valid?
-use regex::Regex;
-use std::collections::{HashMap, HashSet};
-use std::slice::Iter;
-
-const INPUT: &str = include_str!("../input/day_04.txt");
-
-pub fn run() {
-    let passports = parse_passports(INPUT);
+use crate::answer::Answer;
+use crate::error::{numbered_blocks, parse_blocks, Error};
+use crate::example::Example;
+use crate::params::Params;
+use schema::{Constraint, Schema};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+pub mod diagnostics;
+pub mod normalize;
+pub mod schema;
+
+const DAY: u8 = 4;
+
+/// The schema file with the rules, and how a field that is given twice is handled
+pub const PARAMS: &[&str] = &["schema", "duplicates"];
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+            ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+            byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+            \n\
+            iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\n\
+            hcl:#cfa07d byr:1929\n\
+            \n\
+            hcl:#ae17e1 iyr:2013\n\
+            eyr:2024\n\
+            ecl:brn pid:760753108 byr:1931\n\
+            hgt:179cm\n\
+            \n\
+            hcl:#cfa07d eyr:2025 pid:166559648\n\
+            iyr:2011 ecl:brn hgt:59in",
+        params: &[],
+        answers: &[Some("2"), None],
+    },
+    Example {
+        input: "\
+            eyr:1972 cid:100\n\
+            hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\
+            \n\
+            iyr:2019\n\
+            hcl:#602927 eyr:1967 hgt:170cm\n\
+            ecl:grn pid:012533040 byr:1946\n\
+            \n\
+            hcl:dab227 iyr:2012\n\
+            ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277\n\
+            \n\
+            hgt:59cm ecl:zzz\n\
+            eyr:2038 hcl:74454a iyr:2023\n\
+            pid:3556412378 byr:2007",
+        params: &[],
+        answers: &[None, Some("0")],
+    },
+    Example {
+        input: "\
+            pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\n\
+            hcl:#623a2f\n\
+            \n\
+            eyr:2029 ecl:blu cid:129 byr:1989\n\
+            iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm\n\
+            \n\
+            hcl:#888785\n\
+            hgt:164cm byr:2001 iyr:2015 cid:88\n\
+            pid:545766238 ecl:hzl\n\
+            eyr:2022\n\
+            \n\
+            iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+        params: &[],
+        answers: &[None, Some("4")],
+    },
+];
+
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    count_valid_passports(
+        input,
+        params,
+        false,
+        "With the Country ID as an optional field, the number of valid passports is",
+    )
+}
 
-    let valid_passports = passports
-        .iter()
-        .filter(|passport| valid_passport(passport, false))
-        .count();
-    println!(
-        "With the Country ID as an optional field, the number of valid passports is: {}",
-        valid_passports
-    );
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    count_valid_passports(
+        input,
+        params,
+        true,
+        "With the stricter data validation, the number of valid passports is",
+    )
+}
 
-    let data_validated_passports = passports
+/// Counts the passports that follow the rules of the puzzle, or of the schema file given by the
+/// `schema` parameter. A field that is in a passport more than once is handled by the
+/// `duplicates` parameter, the last value wins by default. The passports that can't be read
+/// aren't valid, they are in the warnings of the answer with the other warnings.
+fn count_valid_passports(
+    input: &str,
+    params: &Params,
+    validate_data: bool,
+    label: &str,
+) -> Result<Answer, Error> {
+    let duplicates = params.get(DAY, "duplicates", Duplicates::LastWins)?;
+    let parsed = parse_passports_with(input, duplicates);
+    let path = params.get(DAY, "schema", String::new())?;
+    let loaded;
+    let schema = if path.is_empty() {
+        schema::puzzle()
+    } else {
+        loaded = Schema::load(Path::new(&path))
+            .map_err(|reason| Error::invalid_parameter_because(DAY, "schema", &path, reason))?;
+        &loaded
+    };
+
+    let valid_passports = parsed
+        .passports
         .iter()
-        .filter(|passport| valid_passport(passport, true))
+        .filter(|passport| schema.is_valid(passport, validate_data))
         .count();
-    println!(
-        "With the stricter data validation, the number of valid passports is: {}",
-        data_validated_passports
-    )
+    let warnings = parsed.rejected.iter().chain(parsed.warnings.iter());
+    Ok(Answer::new(label, valid_passports).with_warnings(warnings.map(Error::to_string)))
 }
 
-type Passport<'a> = HashMap<Field, &'a str>;
-type ValidationFn = fn(&str) -> bool;
+pub type Passport<'a> = HashMap<Field, &'a str>;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-enum Field {
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Field {
     BirthYear,
     IssueYear,
     ExpirationYear,
@@ -179,10 +274,12 @@ enum Field {
     EyeColor,
     PassportID,
     CountryID,
+    /// A field the puzzle doesn't know, with its code
+    Unknown(String),
 }
 
 impl Field {
-    fn from_code(code: &str) -> Field {
+    pub fn from_code(code: &str) -> Field {
         // byr (Birth Year)
         // iyr (Issue Year)
         // eyr (Expiration Year)
@@ -200,127 +297,253 @@ impl Field {
             "ecl" => Field::EyeColor,
             "pid" => Field::PassportID,
             "cid" => Field::CountryID,
-            _ => panic!("Unknown code found"),
+            _ => Field::Unknown(code.to_string()),
         }
     }
 
-    fn required_validations() -> Iter<'static, (Field, ValidationFn)> {
-        lazy_static! {
-            static ref REQUIRED_FIELDS: Vec<(Field, ValidationFn)> = vec![
-                (Field::BirthYear, valid_birth_year),
-                (Field::IssueYear, valid_issue_year),
-                (Field::ExpirationYear, valid_expiration_year),
-                (Field::Height, valid_height),
-                (Field::HairColor, valid_hair_color),
-                (Field::EyeColor, valid_eye_color),
-                (Field::PassportID, valid_passport_id),
-            ];
+    pub fn code(&self) -> &str {
+        match self {
+            Field::BirthYear => "byr",
+            Field::IssueYear => "iyr",
+            Field::ExpirationYear => "eyr",
+            Field::Height => "hgt",
+            Field::HairColor => "hcl",
+            Field::EyeColor => "ecl",
+            Field::PassportID => "pid",
+            Field::CountryID => "cid",
+            Field::Unknown(code) => code,
         }
-        REQUIRED_FIELDS.iter()
     }
 }
 
-fn valid_passport(passport: &Passport, validate_data: bool) -> bool {
-    Field::required_validations().all(|(field, validate)| match validate_data {
-        true => passport.get(&field).map_or(false, |value| validate(value)),
-        false => passport.contains_key(&field),
-    })
+/// Fields are read from schemas by their code
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(Field::from_code(&code))
+    }
+}
+
+/// Whether the passport follows the rules of the puzzle
+pub fn valid_passport(passport: &Passport, validate_data: bool) -> bool {
+    schema::puzzle().is_valid(passport, validate_data)
 }
 
-fn valid_year(year: &str, min: u16, max: u16) -> bool {
-    let parsed: u16 = year.parse().expect("Year was not a number");
-    parsed >= min && parsed <= max
+pub fn valid_year(year: &str, min: u16, max: u16) -> bool {
+    Constraint::Year { min, max }.check(year).is_ok()
 }
 
-fn valid_birth_year(year: &str) -> bool {
+pub fn valid_birth_year(year: &str) -> bool {
     // byr (Birth Year) - four digits; at least 1920 and at most 2002.
-    valid_year(year, 1920, 2002)
+    schema::puzzle().check(&Field::BirthYear, year).is_ok()
 }
 
-fn valid_issue_year(year: &str) -> bool {
+pub fn valid_issue_year(year: &str) -> bool {
     // iyr (Issue Year) - four digits; at least 2010 and at most 2020.
-    valid_year(year, 2010, 2020)
+    schema::puzzle().check(&Field::IssueYear, year).is_ok()
 }
 
-fn valid_expiration_year(year: &str) -> bool {
+pub fn valid_expiration_year(year: &str) -> bool {
     // eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
-    valid_year(year, 2020, 2030)
+    schema::puzzle().check(&Field::ExpirationYear, year).is_ok()
 }
 
-fn valid_height(height: &str) -> bool {
+pub fn valid_height(height: &str) -> bool {
     // hgt (Height) - a number followed by either cm or in:
     // If cm, the number must be at least 150 and at most 193.
     // If in, the number must be at least 59 and at most 76.
-
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^([0-9]+)(in|cm)$").unwrap();
-    }
-    let captures = RE.captures(height);
-    match captures {
-        Some(groups) => match (groups.get(1), groups.get(2)) {
-            (Some(number_match), Some(unit_match)) => {
-                match (number_match.as_str().parse::<u16>(), unit_match.as_str()) {
-                    (Ok(number), "cm") => number >= 150 && number <= 193,
-                    (Ok(number), "in") => number >= 59 && number <= 76,
-                    _ => false,
-                }
-            }
-            _ => false,
-        },
-
-        _ => false,
-    }
+    schema::puzzle().check(&Field::Height, height).is_ok()
 }
 
-fn valid_hair_color(hair_color: &str) -> bool {
+pub fn valid_hair_color(hair_color: &str) -> bool {
     // hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
-    }
-    RE.is_match(hair_color)
+    schema::puzzle()
+        .check(&Field::HairColor, hair_color)
+        .is_ok()
 }
 
-fn valid_eye_color(eye_color: &str) -> bool {
+pub fn valid_eye_color(eye_color: &str) -> bool {
     // ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
-    lazy_static! {
-        static ref POSSIBLE_EYE_COLORS: HashSet<&'static str> =
-            vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
-                .into_iter()
-                .collect();
-    }
-    POSSIBLE_EYE_COLORS.contains(eye_color)
+    schema::puzzle().check(&Field::EyeColor, eye_color).is_ok()
 }
 
-fn valid_passport_id(passport_id: &str) -> bool {
+pub fn valid_passport_id(passport_id: &str) -> bool {
     // pid (Passport ID) - a nine-digit number, including leading zeroes.
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^[0-9]{9}$").unwrap();
+    schema::puzzle()
+        .check(&Field::PassportID, passport_id)
+        .is_ok()
+}
+
+/// What to do with a field that is in a passport more than once
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Duplicates {
+    /// The passport is rejected
+    Error,
+    /// The last value is used, with a warning
+    Warn,
+    /// The last value is used
+    LastWins,
+}
+
+impl FromStr for Duplicates {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Duplicates, String> {
+        match s {
+            "error" => Ok(Duplicates::Error),
+            "warn" => Ok(Duplicates::Warn),
+            "last-wins" => Ok(Duplicates::LastWins),
+            _ => Err(format!(
+                "Unknown duplicate policy {}, expected error, warn or last-wins",
+                s
+            )),
+        }
+    }
+}
+
+/// The passports of a batch, and the warnings about them
+#[derive(Debug, PartialEq)]
+pub struct ParsedPassports<'a> {
+    pub passports: Vec<Passport<'a>>,
+    /// Why each passport that couldn't be read was left out of `passports`
+    pub rejected: Vec<Error>,
+    /// Pointing at the first line of the passport, like errors do
+    pub warnings: Vec<Error>,
+}
+
+/// Parses the passports, the last value of a field that is in a passport more than once wins.
+/// An entry that isn't like `code:value` is an error.
+pub fn parse_passports(input: &str) -> Result<Vec<Passport<'_>>, Error> {
+    parse_blocks(DAY, input, convert_to_passport)
+}
+
+/// Parses the passports, handling the fields that are in a passport more than once with the
+/// given policy. A passport that can't be read is rejected on its own, the others are still
+/// parsed.
+pub fn parse_passports_with(input: &str, duplicates: Duplicates) -> ParsedPassports<'_> {
+    let mut parsed = ParsedPassports {
+        passports: vec![],
+        rejected: vec![],
+        warnings: vec![],
+    };
+    for (line, block) in numbered_blocks(input) {
+        match convert_entries(block, duplicates) {
+            Ok((passport, duplicated)) => {
+                parsed.passports.push(passport);
+                parsed.warnings.extend(
+                    duplicated
+                        .into_iter()
+                        .map(|reason| Error::invalid_line(DAY, line, block, reason)),
+                );
+            }
+            Err(reason) => parsed
+                .rejected
+                .push(Error::invalid_line(DAY, line, block, reason)),
+        }
     }
-    RE.is_match(passport_id)
+    parsed
 }
 
-fn parse_passports(input: &str) -> Vec<Passport> {
-    input
-        .split("\n\n")
-        .map(|block| convert_to_passport(block))
-        .collect()
+pub fn convert_to_passport(block: &str) -> Result<Passport<'_>, String> {
+    convert_entries(block, Duplicates::LastWins).map(|(passport, _)| passport)
 }
 
-fn convert_to_passport(block: &str) -> Passport {
+/// Converts the entries of a passport, together with the reasons for warnings about duplicated
+/// fields. Values can contain colons themselves, only the first one separates the code.
+fn convert_entries(
+    block: &str,
+    duplicates: Duplicates,
+) -> Result<(Passport<'_>, Vec<String>), String> {
     let mut passport = Passport::new();
+    let mut warnings = vec![];
     for entry in block.split_whitespace() {
-        let sections: Vec<_> = entry.split(':').collect();
-        assert_eq!(sections.len(), 2, "There are not 2 sections in an entry");
-        let field = Field::from_code(sections[0]);
-        let value = sections[1];
-        passport.insert(field, value);
+        let (code, value) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("expected an entry like code:value, got {}", entry))?;
+        if let Some(previous) = passport.insert(Field::from_code(code), value) {
+            let reason = format!("{} is in the passport more than once", code);
+            match duplicates {
+                Duplicates::Error => return Err(reason),
+                Duplicates::Warn => {
+                    warnings.push(format!("{}, {} replaces {}", reason, value, previous))
+                }
+                Duplicates::LastWins => (),
+            }
+        }
     }
-    passport
+    Ok((passport, warnings))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
+
+    #[test]
+    fn test_schema_parameter() {
+        let mut params = Params::new();
+        params.set("schema", "no/schema.toml");
+
+        match part_1(EXAMPLES[0].input, &params) {
+            Err(Error::InvalidParameter {
+                name,
+                value,
+                reason: Some(reason),
+                ..
+            }) => {
+                assert_eq!(
+                    (name.as_str(), value.as_str()),
+                    ("schema", "no/schema.toml")
+                );
+                assert!(reason.starts_with("Could not read no/schema.toml"));
+            }
+            other => panic!("expected an invalid schema parameter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_duplicates_parameter() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+            byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+            \n\
+            ecl:amb ecl:brn pid:028048884 eyr:2023 hcl:#cfa07d\n\
+            byr:1929 iyr:2013 hgt:150cm";
+        let mut params = Params::new();
+
+        assert_eq!(
+            part_2(input, &params).map(|answer| answer.value.to_string()),
+            Ok("2".to_string())
+        );
+        params.set("duplicates", "error");
+        let answer = part_2(input, &params).unwrap();
+        assert_eq!(answer.value.to_string(), "1");
+        assert_eq!(
+            answer.warnings,
+            vec![Error::invalid_line(
+                DAY,
+                4,
+                "ecl:amb ecl:brn pid:028048884 eyr:2023 hcl:#cfa07d\nbyr:1929 iyr:2013 hgt:150cm",
+                "ecl is in the passport more than once"
+            )
+            .to_string()]
+        );
+        params.set("duplicates", "first-wins");
+        assert_eq!(
+            part_2(input, &params),
+            Err(Error::invalid_parameter(DAY, "duplicates", "first-wins"))
+        );
+    }
+
+    #[test]
+    fn test_field_code() {
+        assert_eq!(Field::from_code(Field::Height.code()), Field::Height);
+        assert_eq!(Field::from_code("url").code(), "url");
+    }
 
     #[test]
     fn test_parse_passports() {
@@ -365,7 +588,95 @@ mod tests {
 
         let expected = vec![p1, p2, p3];
 
-        assert_eq!(parse_passports(input), expected);
+        assert_eq!(parse_passports(input), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_passports_unknown_code() {
+        let input = "ecl:gry pid:860033327\n\
+            \n\
+            iyr:2013 url:http://x:80\n\
+            hcl:#cfa07d xyz:1929";
+
+        let passports = parse_passports(input).unwrap();
+        assert_eq!(passports.len(), 2);
+        assert_eq!(
+            passports[1].get(&Field::Unknown("url".to_string())),
+            Some(&"http://x:80")
+        );
+        assert_eq!(passports[1].get(&Field::from_code("xyz")), Some(&"1929"));
+        assert_eq!(
+            parse_passports("ecl:gry\n\niyr:2013 hcl"),
+            Err(Error::invalid_line(
+                DAY,
+                3,
+                "iyr:2013 hcl",
+                "expected an entry like code:value, got hcl"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let input = "ecl:gry pid:860033327\n\
+            \n\
+            iyr:2013 ecl:amb\n\
+            ecl:brn";
+
+        assert_eq!(
+            parse_passports(input).unwrap()[1].get(&Field::EyeColor),
+            Some(&"brn")
+        );
+        let parsed = parse_passports_with(input, Duplicates::Error);
+        assert_eq!(parsed.passports.len(), 1);
+        assert_eq!(
+            parsed.rejected,
+            vec![Error::invalid_line(
+                DAY,
+                3,
+                "iyr:2013 ecl:amb\necl:brn",
+                "ecl is in the passport more than once"
+            )]
+        );
+        let parsed = parse_passports_with(input, Duplicates::Warn);
+        assert_eq!(parsed.passports[1].get(&Field::EyeColor), Some(&"brn"));
+        assert_eq!(
+            parsed.warnings,
+            vec![Error::invalid_line(
+                DAY,
+                3,
+                "iyr:2013 ecl:amb\necl:brn",
+                "ecl is in the passport more than once, brn replaces amb"
+            )]
+        );
+        let parsed = parse_passports_with(input, Duplicates::LastWins);
+        assert_eq!(parsed.passports[1].get(&Field::EyeColor), Some(&"brn"));
+        assert_eq!(parsed.warnings, vec![]);
+        assert_eq!("last-wins".parse(), Ok(Duplicates::LastWins));
+        assert!("first-wins".parse::<Duplicates>().is_err());
+    }
+
+    #[test]
+    fn test_bad_entry_rejects_only_its_passport() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+            byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+            \n\
+            iyr:2013 hcl";
+        let parsed = parse_passports_with(input, Duplicates::LastWins);
+
+        assert_eq!(parsed.passports.len(), 1);
+        assert_eq!(
+            parsed.rejected,
+            vec![Error::invalid_line(
+                DAY,
+                4,
+                "iyr:2013 hcl",
+                "expected an entry like code:value, got hcl"
+            )]
+        );
+        let answer = part_1(input, &Params::new()).unwrap();
+        assert_eq!(answer.value.to_string(), "1");
+        assert_eq!(answer.warnings, vec![parsed.rejected[0].to_string()]);
     }
 
     #[test]
@@ -447,6 +758,7 @@ mod tests {
     #[test]
     fn test_valid_year_false() {
         assert!(!valid_birth_year("2003"));
+        assert!(!valid_birth_year("19x0"));
     }
 
     #[test]
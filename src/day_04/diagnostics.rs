@@ -0,0 +1,276 @@
+//! Explains why passports are invalid, field by field.
+//!
+//! A `Report` lists what is wrong with one passport: the required fields it misses, the values
+//! that don't follow their constraint, and the codes the schema doesn't know. A `Summary` adds up
+//! the problems of a whole batch to find the most common ones, and counts the unknown codes on
+//! their own.
+use super::schema::{Schema, Violation};
+use super::{Field, Passport};
+use std::collections::HashMap;
+use std::fmt;
+
+/// What is wrong with a passport
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    /// The number of the passport in the batch, starting at 1
+    pub number: usize,
+    pub missing: Vec<Field>,
+    pub invalid: Vec<InvalidValue>,
+    /// The fields of the passport that aren't in the schema, they don't make it invalid
+    pub unknown: Vec<Field>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvalidValue {
+    pub field: Field,
+    pub value: String,
+    pub violation: Violation,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.invalid.is_empty()
+    }
+
+    /// Every reason the passport is invalid, like `missing byr` or `hgt bad unit`. Unknown codes
+    /// aren't problems, they are left out.
+    pub fn problems(&self) -> Vec<String> {
+        let missing = self
+            .missing
+            .iter()
+            .map(|field| format!("missing {}", field.code()));
+        let invalid = self
+            .invalid
+            .iter()
+            .map(|invalid| format!("{} {}", invalid.field.code(), invalid.violation.cause));
+        missing.chain(invalid).collect()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Passport {}: ", self.number)?;
+        write!(f, "{}", if self.is_valid() { "valid" } else { "invalid" })?;
+        for field in self.missing.iter() {
+            write!(f, "\n    missing {}", field.code())?;
+        }
+        for invalid in self.invalid.iter() {
+            write!(
+                f,
+                "\n    {} {}: {}",
+                invalid.field.code(),
+                invalid.violation.cause,
+                invalid.violation
+            )?;
+        }
+        for field in self.unknown.iter() {
+            write!(f, "\n    unknown code {}", field.code())?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks every field of every passport against the schema
+pub fn diagnose(schema: &Schema, passports: &[Passport]) -> Vec<Report> {
+    passports
+        .iter()
+        .enumerate()
+        .map(|(index, passport)| diagnose_passport(schema, index + 1, passport))
+        .collect()
+}
+
+fn diagnose_passport(schema: &Schema, number: usize, passport: &Passport) -> Report {
+    let mut missing = vec![];
+    let mut invalid = vec![];
+    for rule in schema.fields.iter() {
+        match passport.get(&rule.code) {
+            Some(value) => {
+                if let Err(violation) = rule.check(value) {
+                    invalid.push(InvalidValue {
+                        field: rule.code.clone(),
+                        value: value.to_string(),
+                        violation,
+                    });
+                }
+            }
+            None if rule.required => missing.push(rule.code.clone()),
+            None => (),
+        }
+    }
+    let mut unknown: Vec<Field> = passport
+        .keys()
+        .filter(|field| schema.rule(field).is_none())
+        .cloned()
+        .collect();
+    unknown.sort_by(|a, b| a.code().cmp(b.code()));
+    Report {
+        number,
+        missing,
+        invalid,
+        unknown,
+    }
+}
+
+/// The problems of a batch of passports, counted
+#[derive(Debug, PartialEq, Clone)]
+pub struct Summary {
+    pub passports: usize,
+    pub valid: usize,
+    /// Every problem and the number of passports that have it, the most common first
+    pub problems: Vec<(String, usize)>,
+    /// The codes the schema doesn't know and the number of passports that have them, apart from
+    /// the problems as they don't make a passport invalid
+    pub unknown: Vec<(String, usize)>,
+}
+
+impl Summary {
+    pub fn of(reports: &[Report]) -> Summary {
+        let unknown = reports
+            .iter()
+            .flat_map(|report| report.unknown.iter().map(|field| field.code().to_string()));
+        Summary {
+            passports: reports.len(),
+            valid: reports.iter().filter(|report| report.is_valid()).count(),
+            problems: most_common(reports.iter().flat_map(|report| report.problems())),
+            unknown: most_common(unknown),
+        }
+    }
+}
+
+/// Counts the names, the most common first and names as common as each other by name
+fn most_common(names: impl Iterator<Item = String>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for name in names {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+    let mut counted: Vec<_> = counts.into_iter().collect();
+    counted.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counted
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} passports are valid",
+            self.valid, self.passports
+        )?;
+        for (problem, count) in self.problems.iter() {
+            write!(f, "\n{:>6}  {}", count, problem)?;
+        }
+        if !self.unknown.is_empty() {
+            write!(f, "\nUnknown codes, they don't make a passport invalid:")?;
+            for (code, count) in self.unknown.iter() {
+                write!(f, "\n{:>6}  {}", count, code)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_04::schema::{puzzle, Cause};
+    use crate::day_04::{parse_passports, EXAMPLES};
+
+    #[test]
+    fn test_diagnose() {
+        let passports = parse_passports(EXAMPLES[1].input).unwrap();
+        let reports = diagnose(puzzle(), &passports);
+
+        assert_eq!(reports.len(), 4);
+        assert!(reports.iter().all(|report| !report.is_valid()));
+        assert_eq!(reports[0].missing, vec![]);
+        assert_eq!(
+            reports[0].invalid,
+            vec![
+                InvalidValue {
+                    field: Field::ExpirationYear,
+                    value: "1972".to_string(),
+                    violation: Violation::new(Cause::OutOfRange, "1972 is not from 2020 to 2030"),
+                },
+                InvalidValue {
+                    field: Field::Height,
+                    value: "170".to_string(),
+                    violation: Violation::new(Cause::BadUnit, "170 has no unit, expected cm or in"),
+                },
+                InvalidValue {
+                    field: Field::PassportID,
+                    value: "186cm".to_string(),
+                    violation: Violation::new(Cause::WrongFormat, "186cm doesn't match ^[0-9]{9}$"),
+                },
+            ]
+        );
+        assert_eq!(
+            reports[3].problems(),
+            vec![
+                "byr out of range",
+                "iyr out of range",
+                "eyr out of range",
+                "hgt out of range",
+                "hcl wrong format",
+                "ecl not allowed",
+                "pid wrong format",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_and_unknown() {
+        let schema = Schema::from_toml(
+            "[[fields]]\ncode = \"byr\"\nrequired = true\n\n\
+            [[fields]]\ncode = \"iyr\"\nrequired = true",
+        )
+        .unwrap();
+        let passports = parse_passports("iyr:2015 hgt:180cm cid:12 url:http://x").unwrap();
+        let report = &diagnose(&schema, &passports)[0];
+
+        assert_eq!(report.missing, vec![Field::BirthYear]);
+        assert_eq!(
+            report.unknown,
+            vec![
+                Field::CountryID,
+                Field::Height,
+                Field::Unknown("url".to_string())
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Passport 1: invalid\n    missing byr\n    unknown code cid\n    unknown code hgt\n    \
+            unknown code url"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let passports = parse_passports(
+            "byr:1900 iyr:2015 eyr:2025 hgt:180 hcl:#123abc ecl:brn pid:000000001\n\
+            \n\
+            byr:1990 iyr:2015 eyr:2025 hgt:180 hcl:#123abc ecl:brn\n\
+            \n\
+            byr:1990 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:brn pid:000000001 url:x",
+        )
+        .unwrap();
+        let summary = Summary::of(&diagnose(puzzle(), &passports));
+
+        assert_eq!(
+            summary,
+            Summary {
+                passports: 3,
+                valid: 1,
+                problems: vec![
+                    ("hgt bad unit".to_string(), 2),
+                    ("byr out of range".to_string(), 1),
+                    ("missing pid".to_string(), 1),
+                ],
+                unknown: vec![("url".to_string(), 1)],
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "1 of 3 passports are valid\n     2  hgt bad unit\n     1  byr out of range\n     1  missing pid\n\
+            Unknown codes, they don't make a passport invalid:\n     1  url"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+//! Passports as typed values, to use them elsewhere after validating.
+//!
+//! A `NormalizedPassport` has the years as numbers, the height with its unit, the hair color as
+//! RGB and the eye color as one of the known colors. They are exported as JSON Lines or CSV, and
+//! read back from those. `to_batch` writes them in the format of the puzzle input again, so an
+//! exported file goes back through `parse_passports`.
+use super::schema::puzzle;
+use super::{Field, Passport, DAY};
+use crate::answer::csv_field;
+use crate::error::{convert_lines, numbered_lines, parse_lines, Error};
+use itertools::Itertools;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The codes of the columns of an exported CSV, in order
+const CSV_COLUMNS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct NormalizedPassport {
+    pub birth_year: u16,
+    pub issue_year: u16,
+    pub expiration_year: u16,
+    pub height: Height,
+    pub hair_color: Rgb,
+    pub eye_color: EyeColor,
+    /// Nine digits, including the leading zeroes
+    pub passport_id: String,
+    pub country_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "unit", content = "value")]
+pub enum Height {
+    #[serde(rename = "cm")]
+    Centimeters(u16),
+    #[serde(rename = "in")]
+    Inches(u16),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EyeColor {
+    Amb,
+    Blu,
+    Brn,
+    Gry,
+    Grn,
+    Hzl,
+    Oth,
+}
+
+impl NormalizedPassport {
+    /// Converts a passport that follows the rules of the puzzle, the reason is the first problem
+    /// of an invalid passport. Fields the puzzle doesn't know are left out.
+    pub fn from_passport(passport: &Passport) -> Result<NormalizedPassport, String> {
+        let value = |field: Field| -> Result<&str, String> {
+            let value = passport
+                .get(&field)
+                .ok_or_else(|| format!("missing {}", field.code()))?;
+            puzzle()
+                .check(&field, value)
+                .map_err(|violation| format!("{} {}", field.code(), violation))?;
+            Ok(value)
+        };
+        Ok(NormalizedPassport {
+            birth_year: convert(value(Field::BirthYear)?)?,
+            issue_year: convert(value(Field::IssueYear)?)?,
+            expiration_year: convert(value(Field::ExpirationYear)?)?,
+            height: convert(value(Field::Height)?)?,
+            hair_color: convert(value(Field::HairColor)?)?,
+            eye_color: convert(value(Field::EyeColor)?)?,
+            passport_id: value(Field::PassportID)?.to_string(),
+            country_id: passport
+                .get(&Field::CountryID)
+                .map(|value| value.to_string()),
+        })
+    }
+
+    /// The fields as they are written in a passport, the Country ID only when there is one
+    pub fn fields(&self) -> Vec<(Field, String)> {
+        let mut fields = vec![
+            (Field::BirthYear, self.birth_year.to_string()),
+            (Field::IssueYear, self.issue_year.to_string()),
+            (Field::ExpirationYear, self.expiration_year.to_string()),
+            (Field::Height, self.height.to_string()),
+            (Field::HairColor, self.hair_color.to_string()),
+            (Field::EyeColor, self.eye_color.to_string()),
+            (Field::PassportID, self.passport_id.clone()),
+        ];
+        if let Some(country_id) = &self.country_id {
+            fields.push((Field::CountryID, country_id.clone()));
+        }
+        fields
+    }
+
+    /// The passport in the format of the puzzle input, on a single line. A value with
+    /// whitespace or a colon can't be written without turning into other entries.
+    pub fn to_passport_text(&self) -> Result<String, String> {
+        let fields = self.fields();
+        check_writable(&fields)?;
+        Ok(fields
+            .iter()
+            .map(|(field, value)| format!("{}:{}", field.code(), value))
+            .join(" "))
+    }
+
+    /// Checks the values of a passport read from elsewhere against the rules of the puzzle, and
+    /// that they can be written in a passport
+    fn validate(self) -> Result<NormalizedPassport, String> {
+        let fields = self.fields();
+        check_writable(&fields)?;
+        let passport: Passport = fields
+            .iter()
+            .map(|(field, value)| (field.clone(), value.as_str()))
+            .collect();
+        NormalizedPassport::from_passport(&passport)
+    }
+}
+
+/// Values are separated by whitespace in a passport and their code by a colon, so they can't
+/// contain either
+fn check_writable(fields: &[(Field, String)]) -> Result<(), String> {
+    match fields
+        .iter()
+        .find(|(_, value)| value.contains(|c: char| c.is_whitespace() || c == ':'))
+    {
+        Some((field, value)) => Err(format!(
+            "{} {:?} has whitespace or a colon, it can't be written in a passport",
+            field.code(),
+            value
+        )),
+        None => Ok(()),
+    }
+}
+
+fn convert<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("can't convert {}", value))
+}
+
+/// Converts the passports that follow the rules of the puzzle, the others are left out
+pub fn normalize_valid(passports: &[Passport]) -> Vec<NormalizedPassport> {
+    passports
+        .iter()
+        .filter_map(|passport| NormalizedPassport::from_passport(passport).ok())
+        .collect()
+}
+
+/// Writes the passports in the format of the puzzle input, which `parse_passports` reads. The
+/// first passport with a value that can't be written is an error.
+pub fn to_batch(passports: &[NormalizedPassport]) -> Result<String, String> {
+    let texts: Vec<_> = passports
+        .iter()
+        .map(NormalizedPassport::to_passport_text)
+        .collect::<Result<_, _>>()?;
+    Ok(texts.join("\n\n"))
+}
+
+/// Exports the passports as JSON, one passport on every line
+pub fn to_json_lines(passports: &[NormalizedPassport]) -> String {
+    passports
+        .iter()
+        .map(|passport| {
+            serde_json::to_string(passport).expect("Passports can always be serialized") + "\n"
+        })
+        .collect()
+}
+
+/// Reads passports exported as JSON Lines, they have to follow the rules of the puzzle and have
+/// values that can be written in a passport
+pub fn from_json_lines(input: &str) -> Result<Vec<NormalizedPassport>, Error> {
+    parse_lines(DAY, input, |line| {
+        serde_json::from_str::<NormalizedPassport>(line)
+            .map_err(|error| error.to_string())?
+            .validate()
+    })
+}
+
+/// Exports the passports as CSV, with a column for every field code
+pub fn to_csv(passports: &[NormalizedPassport]) -> String {
+    let mut csv = CSV_COLUMNS.join(",") + "\n";
+    for passport in passports {
+        let fields = passport.fields();
+        let row: Vec<_> = CSV_COLUMNS
+            .iter()
+            .map(|&code| {
+                fields
+                    .iter()
+                    .find(|(field, _)| field.code() == code)
+                    .map_or(String::new(), |(_, value)| csv_field(value))
+            })
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Reads passports exported as CSV, they have to follow the rules of the puzzle and have values
+/// that can be written in a passport
+pub fn from_csv(input: &str) -> Result<Vec<NormalizedPassport>, Error> {
+    let mut lines = numbered_lines(input);
+    let header = CSV_COLUMNS.join(",");
+    match lines.next() {
+        Some((_, line)) if line == header => (),
+        Some((number, line)) => {
+            let reason = format!("expected the header {}", header);
+            return Err(Error::invalid_line(DAY, number, line, reason));
+        }
+        None => return Err(Error::unexpected_end(DAY, "the header of the CSV")),
+    }
+    convert_lines(DAY, lines, |line| {
+        let values = split_csv_row(line)?;
+        if values.len() != CSV_COLUMNS.len() {
+            return Err(format!(
+                "expected {} columns, got {}",
+                CSV_COLUMNS.len(),
+                values.len()
+            ));
+        }
+        let passport: Passport = CSV_COLUMNS
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(code, value)| (Field::from_code(code), value.as_str()))
+            .collect();
+        NormalizedPassport::from_passport(&passport)?.validate()
+    })
+}
+
+/// Splits a row of CSV into its fields, undoing the quotes of `csv_field`
+fn split_csv_row(row: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(format!("a quote isn't closed in {}", row));
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Height::Centimeters(value) => write!(f, "{}cm", value),
+            Height::Inches(value) => write!(f, "{}in", value),
+        }
+    }
+}
+
+impl FromStr for Height {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Height, String> {
+        let number = |value: &str| {
+            value
+                .parse()
+                .map_err(|_| format!("expected a height like 183cm, got {}", s))
+        };
+        if let Some(value) = s.strip_suffix("cm") {
+            Ok(Height::Centimeters(number(value)?))
+        } else if let Some(value) = s.strip_suffix("in") {
+            Ok(Height::Inches(number(value)?))
+        } else {
+            Err(format!("expected a height in cm or in, got {}", s))
+        }
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rgb, String> {
+        let invalid = || format!("expected a color like #623a2f, got {}", s);
+        let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16);
+        Ok(Rgb {
+            red: channel(0).map_err(|_| invalid())?,
+            green: channel(2).map_err(|_| invalid())?,
+            blue: channel(4).map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Colors are written like in the passports, as #rrggbb
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rgb, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl EyeColor {
+    pub fn code(&self) -> &'static str {
+        match self {
+            EyeColor::Amb => "amb",
+            EyeColor::Blu => "blu",
+            EyeColor::Brn => "brn",
+            EyeColor::Gry => "gry",
+            EyeColor::Grn => "grn",
+            EyeColor::Hzl => "hzl",
+            EyeColor::Oth => "oth",
+        }
+    }
+}
+
+impl fmt::Display for EyeColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for EyeColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<EyeColor, String> {
+        match s {
+            "amb" => Ok(EyeColor::Amb),
+            "blu" => Ok(EyeColor::Blu),
+            "brn" => Ok(EyeColor::Brn),
+            "gry" => Ok(EyeColor::Gry),
+            "grn" => Ok(EyeColor::Grn),
+            "hzl" => Ok(EyeColor::Hzl),
+            "oth" => Ok(EyeColor::Oth),
+            _ => Err(format!("unknown eye color {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_04::{parse_passports, EXAMPLES};
+
+    fn normalized() -> Vec<NormalizedPassport> {
+        normalize_valid(&parse_passports(EXAMPLES[2].input).unwrap())
+    }
+
+    #[test]
+    fn test_from_passport() {
+        let passports = normalized();
+
+        assert_eq!(passports.len(), 4);
+        assert_eq!(
+            passports[0],
+            NormalizedPassport {
+                birth_year: 1980,
+                issue_year: 2012,
+                expiration_year: 2030,
+                height: Height::Inches(74),
+                hair_color: Rgb {
+                    red: 0x62,
+                    green: 0x3a,
+                    blue: 0x2f
+                },
+                eye_color: EyeColor::Grn,
+                passport_id: "087499704".to_string(),
+                country_id: None,
+            }
+        );
+        assert_eq!(passports[1].country_id, Some("129".to_string()));
+    }
+
+    #[test]
+    fn test_from_invalid_passport() {
+        let passports = parse_passports(EXAMPLES[1].input).unwrap();
+
+        assert_eq!(normalize_valid(&passports), vec![]);
+        assert_eq!(
+            NormalizedPassport::from_passport(&passports[0]),
+            Err("eyr 1972 is not from 2020 to 2030".to_string())
+        );
+        assert_eq!(
+            NormalizedPassport::from_passport(&passports[1]),
+            Err("eyr 1967 is not from 2020 to 2030".to_string())
+        );
+        assert_eq!(
+            NormalizedPassport::from_passport(&parse_passports("byr:1990").unwrap()[0]),
+            Err("missing iyr".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_json_lines() {
+        let json = to_json_lines(&normalized()[..1]);
+
+        assert_eq!(
+            json,
+            "{\"birth_year\":1980,\"issue_year\":2012,\"expiration_year\":2030,\
+            \"height\":{\"unit\":\"in\",\"value\":74},\"hair_color\":\"#623a2f\",\
+            \"eye_color\":\"grn\",\"passport_id\":\"087499704\",\"country_id\":null}\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&normalized()[..2]),
+            "byr,iyr,eyr,hgt,hcl,ecl,pid,cid\n\
+            1980,2012,2030,74in,#623a2f,grn,087499704,\n\
+            1989,2014,2029,165cm,#a97842,blu,896056539,129\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let passports = normalized();
+        let batch = to_batch(&passports).unwrap();
+
+        assert_eq!(
+            normalize_valid(&parse_passports(&batch).unwrap()),
+            passports
+        );
+        assert_eq!(
+            from_json_lines(&to_json_lines(&passports)),
+            Ok(passports.clone())
+        );
+        assert_eq!(from_csv(&to_csv(&passports)), Ok(passports.clone()));
+
+        let mut quoted = passports[..1].to_vec();
+        quoted[0].country_id = Some("a,\"b\"".to_string());
+        assert_eq!(from_csv(&to_csv(&quoted)), Ok(quoted));
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let json = to_json_lines(&normalized()[..2]).replace("1989", "1800");
+        assert_eq!(
+            from_json_lines(&json).map_err(|error| error.to_string()),
+            Err(format!(
+                "Day 4, line 2: byr 1800 is not from 1920 to 2002\n    {}",
+                json.lines().nth(1).unwrap()
+            ))
+        );
+        assert_eq!(
+            from_csv("byr,pid\n"),
+            Err(Error::invalid_line(
+                DAY,
+                1,
+                "byr,pid",
+                "expected the header byr,iyr,eyr,hgt,hcl,ecl,pid,cid"
+            ))
+        );
+        assert_eq!(
+            from_csv(""),
+            Err(Error::unexpected_end(DAY, "the header of the CSV"))
+        );
+        assert_eq!(
+            split_csv_row("1,\"a,\"\"b\"\"\",,2"),
+            Ok(vec![
+                "1".to_string(),
+                "a,\"b\"".to_string(),
+                String::new(),
+                "2".to_string()
+            ])
+        );
+        assert!(split_csv_row("1,\"a").is_err());
+    }
+
+    #[test]
+    fn test_values_that_cant_be_written() {
+        let mut passports = normalized()[..1].to_vec();
+        passports[0].country_id = Some("x byr:1900".to_string());
+        let reason =
+            "cid \"x byr:1900\" has whitespace or a colon, it can't be written in a passport";
+
+        assert_eq!(to_batch(&passports), Err(reason.to_string()));
+        let csv = to_csv(&passports);
+        assert_eq!(
+            from_csv(&csv),
+            Err(Error::invalid_line(
+                DAY,
+                2,
+                csv.lines().nth(1).unwrap(),
+                reason
+            ))
+        );
+        let json = to_json_lines(&passports);
+        assert_eq!(
+            from_json_lines(&json),
+            Err(Error::invalid_line(DAY, 1, json.trim_end(), reason))
+        );
+        passports[0].country_id = Some("a:b".to_string());
+        assert!(from_json_lines(&to_json_lines(&passports)).is_err());
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!("183cm".parse(), Ok(Height::Centimeters(183)));
+        assert!("183".parse::<Height>().is_err());
+        assert_eq!(Height::Inches(59).to_string(), "59in");
+        assert!("#12345g".parse::<Rgb>().is_err());
+        assert_eq!(
+            "#fffffd".parse::<Rgb>().map(|rgb| rgb.to_string()),
+            Ok("#fffffd".to_string())
+        );
+        assert_eq!("hzl".parse(), Ok(EyeColor::Hzl));
+        assert!("zzz".parse::<EyeColor>().is_err());
+    }
+}
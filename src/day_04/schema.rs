@@ -0,0 +1,365 @@
+//! The rules a passport has to follow, declared in a schema instead of in code.
+//!
+//! A schema lists the field codes, whether each is required, and the constraint on its value.
+//! The rules of the puzzle are in `passport.toml` next to this file. Other schemas are loaded
+//! from TOML or JSON files, so other documents or changed rules need no recompile:
+//!
+//! ```toml
+//! [[fields]]
+//! code = "hgt"
+//! required = true
+//! constraint = { type = "measure", units = { cm = { min = 150, max = 193 } } }
+//! ```
+//!
+//! The constraints are a `year` with a `min` and `max`, a `measure` with a range for every
+//! unit, a regex `pattern`, and `one-of` a set of `values`.
+use super::{Field, Passport};
+use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const PUZZLE_SCHEMA: &str = include_str!("passport.toml");
+
+#[derive(Debug, Deserialize)]
+pub struct Schema {
+    pub fields: Vec<FieldRule>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FieldRule {
+    pub code: Field,
+    #[serde(default)]
+    pub required: bool,
+    /// Any value is valid without a constraint
+    pub constraint: Option<Constraint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Constraint {
+    /// A year from `min` to `max`
+    Year { min: u16, max: u16 },
+    /// A number followed by one of the units, within the range of that unit
+    Measure { units: BTreeMap<String, Range> },
+    /// A value matching a regular expression
+    Pattern { pattern: Pattern },
+    /// Exactly one of the values
+    OneOf { values: Vec<String> },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct Range {
+    pub min: u32,
+    pub max: u32,
+}
+
+/// A regular expression, compiled when the schema is loaded
+#[derive(Debug)]
+pub struct Pattern(pub Regex);
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Pattern, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern)
+            .map(Pattern)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Schema {
+    pub fn from_toml(text: &str) -> Result<Schema, String> {
+        toml::from_str(text).map_err(|error| error.to_string())
+    }
+
+    pub fn from_json(text: &str) -> Result<Schema, String> {
+        serde_json::from_str(text).map_err(|error| error.to_string())
+    }
+
+    /// Loads a schema from a JSON file when it ends in `.json`, from TOML otherwise
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+        let schema = match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Schema::from_json(&text),
+            _ => Schema::from_toml(&text),
+        };
+        schema.map_err(|error| format!("Invalid schema {}: {}", path.display(), error))
+    }
+
+    /// Whether the passport has all required fields, and with `validate_data` whether the
+    /// values of its fields follow their constraints as well
+    pub fn is_valid(&self, passport: &Passport, validate_data: bool) -> bool {
+        self.fields
+            .iter()
+            .all(|rule| match passport.get(&rule.code) {
+                Some(value) => !validate_data || rule.check(value).is_ok(),
+                None => !rule.required,
+            })
+    }
+
+    /// The rule of a field, `None` when the field isn't in the schema
+    pub fn rule(&self, field: &Field) -> Option<&FieldRule> {
+        self.fields.iter().find(|rule| &rule.code == field)
+    }
+
+    /// Checks the value of a field, the fields that aren't in the schema can have any value
+    pub fn check(&self, field: &Field, value: &str) -> Result<(), Violation> {
+        match self.rule(field) {
+            Some(rule) => rule.check(value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Schema {
+    /// The rules of the puzzle
+    fn default() -> Schema {
+        Schema::from_toml(PUZZLE_SCHEMA).expect("the schema of the puzzle is valid")
+    }
+}
+
+/// The rules of the puzzle, loaded once
+pub fn puzzle() -> &'static Schema {
+    lazy_static! {
+        static ref PUZZLE: Schema = Schema::default();
+    }
+    &PUZZLE
+}
+
+impl FieldRule {
+    pub fn check(&self, value: &str) -> Result<(), Violation> {
+        match &self.constraint {
+            Some(constraint) => constraint.check(value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Constraint {
+    /// Checks a value, giving the reason when it doesn't follow the constraint
+    pub fn check(&self, value: &str) -> Result<(), Violation> {
+        match self {
+            Constraint::Year { min, max } => {
+                let year: u16 = value.parse().map_err(|_| {
+                    Violation::new(Cause::WrongFormat, format!("{} is not a year", value))
+                })?;
+                if year < *min || year > *max {
+                    let reason = format!("{} is not from {} to {}", year, min, max);
+                    return Err(Violation::new(Cause::OutOfRange, reason));
+                }
+            }
+            Constraint::Measure { units } => {
+                let split = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                let (number, unit) = value.split_at(split);
+                let expected = units.keys().join(" or ");
+                let range = units.get(unit).ok_or_else(|| {
+                    let reason = match unit {
+                        "" => format!("{} has no unit, expected {}", value, expected),
+                        _ => format!("unknown unit {}, expected {}", unit, expected),
+                    };
+                    Violation::new(Cause::BadUnit, reason)
+                })?;
+                let number: u32 = number.parse().map_err(|_| {
+                    let reason = format!("{} doesn't start with a number", value);
+                    Violation::new(Cause::WrongFormat, reason)
+                })?;
+                if number < range.min || number > range.max {
+                    let reason = format!(
+                        "{} is not from {}{} to {}{}",
+                        value, range.min, unit, range.max, unit
+                    );
+                    return Err(Violation::new(Cause::OutOfRange, reason));
+                }
+            }
+            Constraint::Pattern { pattern } => {
+                if !pattern.0.is_match(value) {
+                    let reason = format!("{} doesn't match {}", value, pattern.0);
+                    return Err(Violation::new(Cause::WrongFormat, reason));
+                }
+            }
+            Constraint::OneOf { values } => {
+                if !values.iter().any(|allowed| allowed == value) {
+                    let reason = format!("{} is not one of {}", value, values.join(", "));
+                    return Err(Violation::new(Cause::NotAllowed, reason));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The kind of problem with a value, to group the problems of many passports by
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Cause {
+    /// A number or year outside of its range
+    OutOfRange,
+    /// A measure without a unit, or with a unit that isn't allowed
+    BadUnit,
+    /// A value that doesn't look like it should, like a year that isn't a number
+    WrongFormat,
+    /// A value that isn't one of the allowed values
+    NotAllowed,
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            Cause::OutOfRange => "out of range",
+            Cause::BadUnit => "bad unit",
+            Cause::WrongFormat => "wrong format",
+            Cause::NotAllowed => "not allowed",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+/// Why a value doesn't follow its constraint
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Violation {
+    pub cause: Cause,
+    pub reason: String,
+}
+
+impl Violation {
+    pub fn new(cause: Cause, reason: impl Into<String>) -> Violation {
+        Violation {
+            cause,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_04::{parse_passports, EXAMPLES};
+
+    #[test]
+    fn test_puzzle_schema() {
+        let schema = puzzle();
+        let valid = |input: &str, validate_data: bool| {
+            parse_passports(input)
+                .unwrap()
+                .iter()
+                .filter(|passport| schema.is_valid(passport, validate_data))
+                .count()
+        };
+
+        assert_eq!(schema.fields.len(), 8);
+        assert_eq!(valid(EXAMPLES[0].input, false), 2);
+        assert_eq!(valid(EXAMPLES[1].input, true), 0);
+        assert_eq!(valid(EXAMPLES[2].input, true), 4);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let schema = puzzle();
+        let check = |field, value| {
+            schema
+                .check(field, value)
+                .map_err(|violation| violation.reason)
+        };
+
+        assert_eq!(check(&Field::BirthYear, "2002"), Ok(()));
+        assert_eq!(
+            check(&Field::BirthYear, "2003"),
+            Err("2003 is not from 1920 to 2002".to_string())
+        );
+        assert_eq!(
+            check(&Field::IssueYear, "20x0"),
+            Err("20x0 is not a year".to_string())
+        );
+        assert_eq!(check(&Field::Height, "60in"), Ok(()));
+        assert_eq!(
+            check(&Field::Height, "190in"),
+            Err("190in is not from 59in to 76in".to_string())
+        );
+        assert_eq!(
+            check(&Field::Height, "190"),
+            Err("190 has no unit, expected cm or in".to_string())
+        );
+        assert_eq!(
+            check(&Field::Height, "190ft"),
+            Err("unknown unit ft, expected cm or in".to_string())
+        );
+        assert_eq!(
+            check(&Field::HairColor, "123abc"),
+            Err("123abc doesn't match ^#[0-9a-f]{6}$".to_string())
+        );
+        assert_eq!(
+            check(&Field::EyeColor, "wat"),
+            Err("wat is not one of amb, blu, brn, gry, grn, hzl, oth".to_string())
+        );
+        assert_eq!(check(&Field::CountryID, "anything"), Ok(()));
+    }
+
+    #[test]
+    fn test_causes() {
+        let cause = |field, value| puzzle().check(field, value).unwrap_err().cause;
+
+        assert_eq!(cause(&Field::BirthYear, "1900"), Cause::OutOfRange);
+        assert_eq!(cause(&Field::BirthYear, "19x0"), Cause::WrongFormat);
+        assert_eq!(cause(&Field::Height, "190"), Cause::BadUnit);
+        assert_eq!(cause(&Field::Height, "cm"), Cause::WrongFormat);
+        assert_eq!(cause(&Field::HairColor, "#12345"), Cause::WrongFormat);
+        assert_eq!(cause(&Field::EyeColor, "zzz"), Cause::NotAllowed);
+    }
+
+    #[test]
+    fn test_from_json() {
+        let schema = Schema::from_json(
+            r#"{"fields": [
+                {"code": "ecl", "required": true,
+                 "constraint": {"type": "one-of", "values": ["gry", "pnk"]}},
+                {"code": "cid"}
+            ]}"#,
+        )
+        .unwrap();
+        let passports = parse_passports("ecl:pnk\n\necl:amb cid:1\n\ncid:2").unwrap();
+
+        assert_eq!(
+            passports
+                .iter()
+                .map(|passport| schema.is_valid(passport, true))
+                .collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+        assert!(!schema.fields[1].required);
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(Schema::from_toml("[[fields]]\ncode = 12").is_err());
+        // other documents have other codes
+        assert_eq!(
+            Schema::from_toml("[[fields]]\ncode = \"url\"")
+                .unwrap()
+                .fields[0]
+                .code,
+            Field::Unknown("url".to_string())
+        );
+        assert!(Schema::from_toml(
+            "[[fields]]\ncode = \"pid\"\nconstraint = { type = \"pattern\", pattern = \"(\" }"
+        )
+        .is_err());
+        assert!(Schema::from_json(
+            r#"{"fields": [{"code": "byr", "constraint": {"type": "age"}}]}"#
+        )
+        .is_err());
+        assert!(Schema::load(Path::new("no/schema.toml"))
+            .unwrap_err()
+            .starts_with("Could not read no/schema.toml"));
+    }
+}
@@ -71,24 +71,273 @@
 /// and -1 from yours will be in your list.
 ///
 /// What is the ID of your seat?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 
-const INPUT: &str = include_str!("../input/day_05.txt");
+const DAY: u8 = 5;
 
-pub fn run() {
-    let mut seat_ids: Vec<_> = INPUT.lines().map(|line| convert_to_seat_id(line)).collect();
-    seat_ids.sort();
+/// The size of the plane and the letters of its boarding passes
+pub const PARAMS: &[&str] = &["rows", "columns", "row_letters", "column_letters"];
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        BFFFBBFRRR\n\
+        FFFBBBFRRR\n\
+        BBFFBBFRLL",
+    params: &[],
+    answers: &[Some("820"), None],
+}];
+
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    let seat_ids = seat_ids(input, &seat_code(params)?)?;
+
+    let highest_seat_id = *seat_ids
+        .last()
+        .ok_or_else(|| Error::no_answer(DAY, "there are no boarding passes"))?;
+    Ok(Answer::new(
+        "The highest seat ID in the list of boarding passes is",
+        highest_seat_id,
+    ))
+}
+
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let seat_ids = seat_ids(input, &seat_code(params)?)?;
+
+    let your_seat_id = find_gap(&seat_ids)
+        .ok_or_else(|| Error::no_answer(DAY, "no free seat is between two taken seats"))?;
+    Ok(Answer::new("Your seat ID is", your_seat_id))
+}
+
+/// The seat code of the puzzle, or of another plane given by the `rows`, `columns`,
+/// `row_letters` and `column_letters` parameters
+fn seat_code(params: &Params) -> Result<SeatCode, Error> {
+    let puzzle = SeatCode::default();
+    let size = |name: &str, default: u32| {
+        let size = params.get(DAY, name, default)?;
+        match check_size(size) {
+            Ok(()) => Ok(size),
+            Err(_) => Err(Error::invalid_parameter(DAY, name, &size.to_string())),
+        }
+    };
+    let letters = |name: &str, (lower, upper): (char, char)| {
+        let letters = params.get(DAY, name, format!("{}{}", lower, upper))?;
+        match letters.chars().collect::<Vec<_>>()[..] {
+            [lower, upper] if check_letters((lower, upper)).is_ok() => Ok((lower, upper)),
+            _ => Err(Error::invalid_parameter(DAY, name, &letters)),
+        }
+    };
+    let rows = size("rows", puzzle.rows)?;
+    let columns = size("columns", puzzle.columns)?;
+    let row_letters = letters("row_letters", puzzle.row_letters)?;
+    let column_letters = letters("column_letters", puzzle.column_letters)?;
+    // the sizes are fine on their own, only together they can have too many seats
+    SeatCode::new(rows, columns, row_letters, column_letters).map_err(|reason| {
+        Error::invalid_parameter_because(DAY, "columns", &columns.to_string(), reason)
+    })
+}
 
-    let highest_seat_id = seat_ids.last().expect("No max found");
-    println!(
-        "The highest seat ID in the list of boarding passes is: {}",
-        highest_seat_id
-    );
+/// A seat of the plane. The ID is the row times the number of columns plus the column, which
+/// numbers the seats from the front to the back.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Seat {
+    pub row: u32,
+    pub column: u32,
+    pub id: u32,
+}
+
+/// How boarding passes code the seats of a plane with binary space partitioning.
+///
+/// The first letters of a code halve the rows, one letter picks the lower half and the other
+/// the upper half, until one row is left. The remaining letters do the same for the columns.
+/// The numbers of rows and columns are powers of two, so every code has the same length.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SeatCode {
+    rows: u32,
+    columns: u32,
+    /// The letters for the lower and upper half of the rows
+    row_letters: (char, char),
+    /// The letters for the lower and upper half of the columns
+    column_letters: (char, char),
+}
+
+impl Default for SeatCode {
+    /// The plane of the puzzle, 128 rows of 8 seats, F(ront) and B(ack) for the rows and L(eft)
+    /// and R(ight) for the columns
+    fn default() -> SeatCode {
+        SeatCode {
+            rows: 128,
+            columns: 8,
+            row_letters: ('F', 'B'),
+            column_letters: ('L', 'R'),
+        }
+    }
+}
+
+impl SeatCode {
+    /// The seat code of a plane, the numbers of rows and columns have to be powers of two with
+    /// an ID for every seat, and the letters of a half have to differ
+    pub fn new(
+        rows: u32,
+        columns: u32,
+        row_letters: (char, char),
+        column_letters: (char, char),
+    ) -> Result<SeatCode, String> {
+        check_size(rows)?;
+        check_size(columns)?;
+        check_letters(row_letters)?;
+        check_letters(column_letters)?;
+        // the IDs go up to the number of seats
+        if rows.checked_mul(columns).is_none() {
+            return Err(format!(
+                "{} rows of {} seats have too many seats",
+                rows, columns
+            ));
+        }
+        Ok(SeatCode {
+            rows,
+            columns,
+            row_letters,
+            column_letters,
+        })
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    pub fn row_letters(&self) -> (char, char) {
+        self.row_letters
+    }
+
+    pub fn column_letters(&self) -> (char, char) {
+        self.column_letters
+    }
+
+    /// The number of letters for the rows, followed by the number of letters for the columns
+    pub fn lengths(&self) -> (usize, usize) {
+        (
+            self.rows.trailing_zeros() as usize,
+            self.columns.trailing_zeros() as usize,
+        )
+    }
+
+    pub fn decode(&self, code: &str) -> Result<Seat, String> {
+        let (row_length, column_length) = self.lengths();
+        let letters: Vec<char> = code.chars().collect();
+        if letters.len() != row_length + column_length {
+            return Err(format!(
+                "expected a boarding pass of {} letters, got {}",
+                row_length + column_length,
+                letters.len()
+            ));
+        }
+        let row = decode_half(&letters[..row_length], self.row_letters, 0)?;
+        let column = decode_half(&letters[row_length..], self.column_letters, row_length)?;
+        self.seat(row, column)
+    }
+
+    pub fn encode(&self, seat: &Seat) -> Result<String, String> {
+        let seat = self.seat(seat.row, seat.column)?;
+        let (row_length, column_length) = self.lengths();
+        let mut code = encode_half(seat.row, row_length, self.row_letters);
+        code.push_str(&encode_half(
+            seat.column,
+            column_length,
+            self.column_letters,
+        ));
+        Ok(code)
+    }
+
+    /// The seat at a row and column, with its ID
+    pub fn seat(&self, row: u32, column: u32) -> Result<Seat, String> {
+        if row >= self.rows || column >= self.columns {
+            return Err(format!(
+                "there is no seat at row {} column {} in a plane of {} rows of {} seats",
+                row, column, self.rows, self.columns
+            ));
+        }
+        Ok(Seat {
+            row,
+            column,
+            id: row * self.columns + column,
+        })
+    }
+}
+
+fn check_size(size: u32) -> Result<(), String> {
+    match size.is_power_of_two() {
+        true => Ok(()),
+        false => Err(format!("{} is not a power of two", size)),
+    }
+}
+
+fn check_letters((lower, upper): (char, char)) -> Result<(), String> {
+    match lower != upper {
+        true => Ok(()),
+        false => Err(format!("{} can't be the letter of both halves", lower)),
+    }
+}
 
-    let your_seat_id = find_gap(&seat_ids).expect("Your seat id not found");
-    println!("Your seat ID is: {}", your_seat_id);
+/// Decodes the letters of the rows or the columns, `offset` is the position of the first letter
+/// in the whole code
+fn decode_half(
+    letters: &[char],
+    (lower, upper): (char, char),
+    offset: usize,
+) -> Result<u32, String> {
+    letters
+        .iter()
+        .enumerate()
+        .try_fold(0, |number, (index, &letter)| {
+            let bit = match letter {
+                _ if letter == lower => 0,
+                _ if letter == upper => 1,
+                _ => {
+                    return Err(format!(
+                        "unknown character {} at position {} in boarding pass, expected {} or {}",
+                        letter,
+                        offset + index + 1,
+                        lower,
+                        upper
+                    ))
+                }
+            };
+            Ok((number << 1) + bit)
+        })
 }
 
-fn find_gap(list: &Vec<u16>) -> Option<u16> {
+fn encode_half(number: u32, length: usize, (lower, upper): (char, char)) -> String {
+    (0..length)
+        .rev()
+        .map(|bit| if number >> bit & 1 == 0 { lower } else { upper })
+        .collect()
+}
+
+/// Parses the boarding passes of the puzzle to their seat IDs, in ascending order
+pub fn parse_seat_ids(input: &str) -> Result<Vec<u32>, Error> {
+    seat_ids(input, &SeatCode::default())
+}
+
+pub fn parse_seats(input: &str, seat_code: &SeatCode) -> Result<Vec<Seat>, Error> {
+    parse_lines(DAY, input, |boarding_pass| seat_code.decode(boarding_pass))
+}
+
+fn seat_ids(input: &str, seat_code: &SeatCode) -> Result<Vec<u32>, Error> {
+    let mut seat_ids: Vec<_> = parse_seats(input, seat_code)?
+        .iter()
+        .map(|seat| seat.id)
+        .collect();
+    seat_ids.sort();
+    Ok(seat_ids)
+}
+
+pub fn find_gap(list: &[u32]) -> Option<u32> {
     // Find gaps in sorted lists
     list.iter()
         .zip(list.iter().skip(1))
@@ -96,22 +345,22 @@ fn find_gap(list: &Vec<u16>) -> Option<u16> {
         .map(|(n1, _)| n1 + 1) // return the number in the middle
 }
 
-fn convert_to_seat_id(boarding_pass: &str) -> u16 {
-    boarding_pass.chars().fold(0, |seat_id, char| {
-        (seat_id << 1)
-            + (match char {
-                'F' => 0,
-                'B' => 1,
-                'L' => 0,
-                'R' => 1,
-                _ => panic!("Unknown character in boarding pass"),
-            })
-    })
+/// The seat ID of a boarding pass of the puzzle
+pub fn convert_to_seat_id(boarding_pass: &str) -> Result<u32, String> {
+    SeatCode::default()
+        .decode(boarding_pass)
+        .map(|seat| seat.id)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_convert_to_seat_id_1() {
@@ -119,7 +368,7 @@ mod tests {
         let boarding_pass = "BFFFBBFRRR";
         let seat_id = 567;
 
-        assert_eq!(convert_to_seat_id(boarding_pass), seat_id);
+        assert_eq!(convert_to_seat_id(boarding_pass), Ok(seat_id));
     }
 
     #[test]
@@ -128,7 +377,7 @@ mod tests {
         let boarding_pass = "FFFBBBFRRR";
         let seat_id = 119;
 
-        assert_eq!(convert_to_seat_id(boarding_pass), seat_id);
+        assert_eq!(convert_to_seat_id(boarding_pass), Ok(seat_id));
     }
 
     #[test]
@@ -137,7 +386,146 @@ mod tests {
         let boarding_pass = "BBFFBBFRLL";
         let seat_id = 820;
 
-        assert_eq!(convert_to_seat_id(boarding_pass), seat_id);
+        assert_eq!(convert_to_seat_id(boarding_pass), Ok(seat_id));
+    }
+
+    #[test]
+    fn test_convert_to_seat_id_invalid() {
+        assert!(convert_to_seat_id("BBFFBBFRLX").is_err());
+    }
+
+    #[test]
+    fn test_convert_to_seat_id_length() {
+        assert_eq!(
+            convert_to_seat_id("BBFFBBFRL"),
+            Err("expected a boarding pass of 10 letters, got 9".to_string())
+        );
+        assert_eq!(
+            convert_to_seat_id("BBFFBBRRLL"),
+            Err("unknown character R at position 7 in boarding pass, expected F or B".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            SeatCode::default().decode("BFFFBBFRRR"),
+            Ok(Seat {
+                row: 70,
+                column: 7,
+                id: 567
+            })
+        );
+
+        let small = SeatCode::new(4, 4, ('0', '1'), ('0', '1')).unwrap();
+        assert_eq!(small.lengths(), (2, 2));
+        assert_eq!(
+            small.decode("1001"),
+            Ok(Seat {
+                row: 2,
+                column: 1,
+                id: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            SeatCode::new(128, 8, ('F', 'B'), ('L', 'R')),
+            Ok(SeatCode::default())
+        );
+        assert_eq!(
+            SeatCode::new(100, 8, ('F', 'B'), ('L', 'R')),
+            Err("100 is not a power of two".to_string())
+        );
+        assert_eq!(
+            SeatCode::new(0, 8, ('F', 'B'), ('L', 'R')),
+            Err("0 is not a power of two".to_string())
+        );
+        assert_eq!(
+            SeatCode::new(128, 8, ('F', 'F'), ('L', 'R')),
+            Err("F can't be the letter of both halves".to_string())
+        );
+        assert_eq!(
+            SeatCode::new(1 << 16, 1 << 16, ('F', 'B'), ('L', 'R')),
+            Err("65536 rows of 65536 seats have too many seats".to_string())
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let seat_code = SeatCode::default();
+
+        assert_eq!(
+            seat_code.encode(&seat_code.seat(102, 4).unwrap()),
+            Ok("BBFFBBFRLL".to_string())
+        );
+        for code in ["BFFFBBFRRR", "FFFBBBFRRR", "FFFFFFFLLL", "BBBBBBBRRR"] {
+            assert_eq!(
+                seat_code.encode(&seat_code.decode(code).unwrap()),
+                Ok(code.to_string())
+            );
+        }
+        assert!(seat_code.seat(128, 0).is_err());
+        assert!(seat_code
+            .encode(&Seat {
+                row: 1,
+                column: 8,
+                id: 16
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_seat_code_parameters() {
+        let mut params =
+            Params::from(&[("rows", "4"), ("columns", "2"), ("row_letters", "ab")][..]);
+
+        assert_eq!(
+            part_1("bbR\nabL\naaR", &params).map(|answer| answer.value.to_string()),
+            Ok("7".to_string())
+        );
+        params.set("columns", "3");
+        assert_eq!(
+            part_1("bbR", &params),
+            Err(Error::invalid_parameter(DAY, "columns", "3"))
+        );
+        params.set("columns", "2");
+        params.set("column_letters", "LLR");
+        assert_eq!(
+            part_1("bbR", &params),
+            Err(Error::invalid_parameter(DAY, "column_letters", "LLR"))
+        );
+        params.set("column_letters", "LR");
+        params.set("rows", "2147483648");
+        params.set("columns", "2147483648");
+        assert_eq!(
+            part_1("bbR", &params),
+            Err(Error::invalid_parameter_because(
+                DAY,
+                "columns",
+                "2147483648",
+                "2147483648 rows of 2147483648 seats have too many seats"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_no_answer() {
+        let params = Params::from(&[("rows", "4"), ("columns", "2"), ("row_letters", "ab")][..]);
+
+        assert_eq!(
+            part_1("", &params),
+            Err(Error::no_answer(DAY, "there are no boarding passes"))
+        );
+        assert_eq!(
+            part_2("bbR\nabL\naaR", &params),
+            Err(Error::no_answer(
+                DAY,
+                "no free seat is between two taken seats"
+            ))
+        );
     }
 
     #[test]
@@ -58,29 +58,64 @@
 ///
 /// For each group, count the number of questions to which anyone answered
 /// "yes". What is the sum of those counts?
+use crate::answer::Answer;
+use crate::error::{parse_blocks, Error};
+use crate::example::Example;
+use crate::params::Params;
 use std::collections::HashSet;
 
-const INPUT: &str = include_str!("../input/day_06.txt");
+const DAY: u8 = 6;
 
-pub fn run() {
-    let groups_answers = load_groups_answers(INPUT);
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        abc\n\
+        \n\
+        a\n\
+        b\n\
+        c\n\
+        \n\
+        ab\n\
+        ac\n\
+        \n\
+        a\n\
+        a\n\
+        a\n\
+        a\n\
+        \n\
+        b",
+    params: &[],
+    answers: &[Some("11"), Some("6")],
+}];
 
-    let anyone_answered_sum = combine_sets(groups_answers.clone(), |set1, set2| {
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let groups_answers = load_groups_answers(input)?;
+
+    let anyone_answered_sum = combine_sets(groups_answers, |set1, set2| {
         set1.union(set2).cloned().collect()
     })
     .map(|any_answers| any_answers.len() as u32)
     .sum::<u32>();
-    println!("Counting the number or questions to which anyone answered \"yes\" to for each group gives: {}", anyone_answered_sum);
+    Ok(Answer::new(
+        "Counting the number or questions to which anyone answered \"yes\" to for each group gives",
+        anyone_answered_sum,
+    ))
+}
 
-    let all_answered_sum = combine_sets(groups_answers.clone(), |set1, set2| {
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let groups_answers = load_groups_answers(input)?;
+
+    let all_answered_sum = combine_sets(groups_answers, |set1, set2| {
         set1.intersection(set2).cloned().collect()
     })
     .map(|any_answers| any_answers.len() as u32)
     .sum::<u32>();
-    println!("Counting the number or questions to which everyone answered \"yes\" to for each group gives: {}", all_answered_sum);
+    Ok(Answer::new(
+        "Counting the number or questions to which everyone answered \"yes\" to for each group gives",
+        all_answered_sum,
+    ))
 }
 
-fn combine_sets(
+pub fn combine_sets(
     sets: Vec<Vec<HashSet<char>>>,
     combine_with: impl Fn(HashSet<char>, &HashSet<char>) -> HashSet<char>,
 ) -> impl Iterator<Item = HashSet<char>> {
@@ -95,13 +130,20 @@ fn combine_sets(
     })
 }
 
-fn load_groups_answers(input: &str) -> Vec<Vec<HashSet<char>>> {
-    input
-        .split("\n\n")
-        .map(|group| {
-            group
-                .lines()
-                .map(|answers| answers.chars().filter(|&c| c >= 'a' && c <= 'z').collect())
+pub fn load_groups_answers(input: &str) -> Result<Vec<Vec<HashSet<char>>>, Error> {
+    parse_blocks(DAY, input, convert_to_group_answers)
+}
+
+pub fn convert_to_group_answers(group: &str) -> Result<Vec<HashSet<char>>, String> {
+    group
+        .lines()
+        .map(|answers| {
+            answers
+                .chars()
+                .map(|c| match c.is_ascii_lowercase() {
+                    true => Ok(c),
+                    false => Err(format!("unknown question {}, expected a to z", c)),
+                })
                 .collect()
         })
         .collect()
@@ -110,25 +152,16 @@ fn load_groups_answers(input: &str) -> Vec<Vec<HashSet<char>>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_load_groups_answers() {
-        let input = "\
-            abc\n\
-            \n\
-            a\n\
-            b\n\
-            c\n\
-            \n\
-            ab\n\
-            ac\n\
-            \n\
-            a\n\
-            a\n\
-            a\n\
-            a\n\
-            \n\
-            b";
+        let input = EXAMPLES[0].input;
 
         let abc: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
         let a: HashSet<char> = ['a'].iter().cloned().collect();
@@ -145,6 +178,6 @@ mod tests {
 
         let expected = vec![g1, g2, g3, g4, g5];
 
-        assert_eq!(load_groups_answers(input), expected);
+        assert_eq!(load_groups_answers(input), Ok(expected));
     }
 }
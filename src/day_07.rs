@@ -82,37 +82,74 @@
 /// In this example, a single shiny gold bag must contain 126 other bags.
 ///
 /// How many individual bags are required inside your single shiny gold bag?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use petgraph::graphmap::DiGraphMap;
 use petgraph::visit::{Bfs, Reversed};
 use petgraph::Direction::Outgoing;
 use regex::Regex;
 
-const INPUT: &str = include_str!("../input/day_07.txt");
+const DAY: u8 = 7;
 
-pub fn run() {
-    let rule_graph = parse_bag_rules(INPUT);
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+            light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+            dark orange bags contain 3 bright white bags, 4 muted yellow bags.\n\
+            bright white bags contain 1 shiny gold bag.\n\
+            muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\n\
+            shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\n\
+            dark olive bags contain 3 faded blue bags, 4 dotted black bags.\n\
+            vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\n\
+            faded blue bags contain no other bags.\n\
+            dotted black bags contain no other bags.",
+        params: &[],
+        answers: &[Some("4"), Some("32")],
+    },
+    Example {
+        input: "\
+            shiny gold bags contain 2 dark red bags.\n\
+            dark red bags contain 2 dark orange bags.\n\
+            dark orange bags contain 2 dark yellow bags.\n\
+            dark yellow bags contain 2 dark green bags.\n\
+            dark green bags contain 2 dark blue bags.\n\
+            dark blue bags contain 2 dark violet bags.\n\
+            dark violet bags contain no other bags.",
+        params: &[],
+        answers: &[None, Some("126")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let rule_graph = parse_bag_rules(input)?;
 
     let mut bfs = Bfs::new(Reversed(&rule_graph), "shiny gold");
     let mut bags_above = 0;
-    while let Some(_) = bfs.next(Reversed(&rule_graph)) {
+    while bfs.next(Reversed(&rule_graph)).is_some() {
         bags_above += 1;
     }
     bags_above -= 1; // remove the shiny gold bag
-    println!(
-        "The amount of bag colors that can contain a shiny gold bag is: {}",
-        bags_above
-    );
+    Ok(Answer::new(
+        "The amount of bag colors that can contain a shiny gold bag is",
+        bags_above,
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let rule_graph = parse_bag_rules(input)?;
 
     let containing_bags = count_containing_bags(&rule_graph, "shiny gold");
-    println!(
-        "The amount of individual bags required inside a single shiny gold bag is: {}",
-        containing_bags
-    );
+    Ok(Answer::new(
+        "The amount of individual bags required inside a single shiny gold bag is",
+        containing_bags,
+    ))
 }
 
-type Graph<'a> = DiGraphMap<&'a str, u32>;
+pub type Graph<'a> = DiGraphMap<&'a str, u32>;
 
-fn count_containing_bags(graph: &Graph, start: &str) -> u32 {
+pub fn count_containing_bags(graph: &Graph, start: &str) -> u32 {
     graph
         .neighbors_directed(start, Outgoing)
         .map(|next| {
@@ -122,35 +159,30 @@ fn count_containing_bags(graph: &Graph, start: &str) -> u32 {
         .sum()
 }
 
-fn parse_bag_rules(input: &str) -> Graph {
-    let weighted_edges: Vec<(&str, &str, u32)> = input
-        .lines()
-        .flat_map(|line| convert_to_edges(line))
-        .collect();
-    Graph::from_edges(weighted_edges)
+pub fn parse_bag_rules(input: &str) -> Result<Graph<'_>, Error> {
+    let weighted_edges = parse_lines(DAY, input, convert_to_edges)?;
+    Ok(Graph::from_edges(weighted_edges.into_iter().flatten()))
 }
 
-fn convert_to_edges(line: &str) -> Vec<(&str, &str, u32)> {
+pub fn convert_to_edges(line: &str) -> Result<Vec<(&str, &str, u32)>, String> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"([0-9]*) ?([a-z]+ [a-z]+) bag").unwrap();
     }
     let mut captures = RE.captures_iter(line);
     let color = captures
         .next()
-        .expect("No first match in line")
+        .filter(|groups| groups.get(0).unwrap().start() == 0)
+        .ok_or_else(|| "expected a rule like: <color> bags contain <n> <color> bags".to_string())?
         .get(2)
-        .expect("No first color")
+        .unwrap()
         .as_str();
 
     captures
-        .filter_map(|groups| {
-            match (
-                groups.get(1).unwrap().as_str().parse(),
-                groups.get(2).unwrap().as_str(),
-            ) {
-                (Ok(amount), connecting_color) => Some((color, connecting_color, amount)),
-                _ => None,
-            }
+        // the bags that contain no other bags don't have an amount
+        .filter(|groups| !groups[1].is_empty())
+        .map(|groups| match groups[1].parse() {
+            Ok(amount) => Ok((color, groups.get(2).unwrap().as_str(), amount)),
+            Err(_) => Err(format!("the amount of {} bags is too large", &groups[2])),
         })
         .collect()
 }
@@ -158,6 +190,12 @@ fn convert_to_edges(line: &str) -> Vec<(&str, &str, u32)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     fn graph_eq(a: &Graph, b: &Graph) -> bool {
         a.nodes().eq(b.nodes()) && a.all_edges().eq(b.all_edges())
@@ -179,16 +217,27 @@ mod tests {
             ("bright white", "shiny gold", 1),
         ]);
 
-        let resulting_graph = parse_bag_rules(input);
+        let resulting_graph = parse_bag_rules(input).unwrap();
         assert!(
             graph_eq(&resulting_graph, &expected_graph),
-            format!(
-                "Graphs are not equal\n\nresult: {:?}\n\nexpected: {:?}\n",
-                resulting_graph, expected_graph
-            )
+            "Graphs are not equal\n\nresult: {:?}\n\nexpected: {:?}\n",
+            resulting_graph,
+            expected_graph
         );
     }
 
+    #[test]
+    fn test_parse_bag_rules_invalid() {
+        let input = "\
+            light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+            Dark orange bags contain 3 bright white bags.";
+
+        assert!(matches!(
+            parse_bag_rules(input),
+            Err(Error::InvalidLine { line: 2, .. })
+        ));
+    }
+
     #[test]
     fn test_count_containing_bags_simple() {
         // shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
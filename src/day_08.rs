@@ -118,23 +118,46 @@
 /// Fix the program so that it terminates normally by changing exactly one jmp
 /// (to nop) or nop (to jmp). What is the value of the accumulator after the
 /// program terminates?
-use itertools::Itertools;
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use std::collections::HashSet;
 use Operation::*;
 use ProgramResult::*;
 
-const INPUT: &str = include_str!("../input/day_08.txt");
+const DAY: u8 = 8;
 
-pub fn run() {
-    let program = load_program(INPUT);
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        nop +0\n\
+        acc +1\n\
+        jmp +4\n\
+        acc +3\n\
+        jmp -3\n\
+        acc -99\n\
+        acc +1\n\
+        jmp -4\n\
+        acc +6",
+    params: &[],
+    answers: &[Some("5"), Some("8")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let program = load_program(input)?;
 
     let (_, result_before_loop) = run_program(&program);
-    println!(
-        "The value of the accumulator before any instruction is executed again is: {}",
-        result_before_loop
-    );
+    Ok(Answer::new(
+        "The value of the accumulator before any instruction is executed again is",
+        result_before_loop,
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let program = load_program(input)?;
 
     // try to fix the program by switching one operation
+    let mut fixed_accumulator = None;
     for i in 0..program.len() {
         let mut instruction = program.get(i).unwrap().clone();
 
@@ -157,15 +180,17 @@ pub fn run() {
         let (result, accumulator) = run_program(&possibly_fixed_program);
 
         if result == Finished {
-            println!(
-                "The value of the accumulator of the fixed program is: {}",
-                accumulator
-            );
+            fixed_accumulator = Some(accumulator);
+            break;
         }
     }
+    Ok(Answer::new(
+        "The value of the accumulator of the fixed program is",
+        fixed_accumulator.expect("No fix for the program found"),
+    ))
 }
 
-fn run_program(program: &Vec<Instruction>) -> (ProgramResult, i32) {
+pub fn run_program(program: &[Instruction]) -> (ProgramResult, i32) {
     let mut index = 0;
     let mut accumulator = 0;
     // keep track of visited location to prevent infinite loops
@@ -208,63 +233,67 @@ fn run_program(program: &Vec<Instruction>) -> (ProgramResult, i32) {
 }
 
 #[derive(Debug, PartialEq, Clone)]
-enum Operation {
+#[allow(clippy::enum_variant_names)]
+pub enum Operation {
     NoOperation,
     Accumulate,
     Jump,
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct Instruction {
-    operation: Operation,
-    argument: i32,
+pub struct Instruction {
+    pub operation: Operation,
+    pub argument: i32,
 }
 
 #[derive(Debug, PartialEq)]
-enum ProgramResult {
+pub enum ProgramResult {
     InfiniteLoop,
     Finished,
     InstructionFailure,
 }
 
-fn load_program(input: &str) -> Vec<Instruction> {
-    input
-        .split_whitespace()
-        .tuples::<(_, _)>()
-        .map(|(op, arg)| match (op, arg.parse()) {
-            ("nop", Ok(argument)) => Instruction {
-                operation: NoOperation,
-                argument,
-            },
-            ("acc", Ok(argument)) => Instruction {
-                operation: Accumulate,
-                argument,
-            },
-            ("jmp", Ok(argument)) => Instruction {
-                operation: Jump,
-                argument,
-            },
-            _ => panic!("Could not parse instruction"),
-        })
-        .collect()
+pub fn load_program(input: &str) -> Result<Vec<Instruction>, Error> {
+    parse_lines(DAY, input, convert_to_instruction)
+}
+
+pub fn convert_to_instruction(line: &str) -> Result<Instruction, String> {
+    let (op, arg) = line
+        .split_once(' ')
+        .ok_or_else(|| "expected an instruction like acc +1".to_string())?;
+    let argument = arg
+        .parse()
+        .map_err(|_| format!("expected a signed number as the argument, got {}", arg))?;
+    let operation = match op {
+        "nop" => NoOperation,
+        "acc" => Accumulate,
+        "jmp" => Jump,
+        _ => {
+            return Err(format!(
+                "unknown operation {}, expected nop, acc or jmp",
+                op
+            ))
+        }
+    };
+    Ok(Instruction {
+        operation,
+        argument,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_load_program() {
-        let input = "\
-            nop +0\n\
-            acc +1\n\
-            jmp +4\n\
-            acc +3\n\
-            jmp -3\n\
-            acc -99\n\
-            acc +1\n\
-            jmp -4\n\
-            acc +6";
+        let input = EXAMPLES[0].input;
 
         let expected_program = vec![
             Instruction {
@@ -305,7 +334,20 @@ mod tests {
             },
         ];
 
-        assert_eq!(load_program(input), expected_program);
+        assert_eq!(load_program(input), Ok(expected_program));
+    }
+
+    #[test]
+    fn test_load_program_invalid() {
+        let input = "nop +0\nadd +1\njmp +4";
+        let expected = Error::invalid_line(
+            DAY,
+            2,
+            "add +1",
+            "unknown operation add, expected nop, acc or jmp",
+        );
+
+        assert_eq!(load_program(input), Err(expected));
     }
 
     #[test]
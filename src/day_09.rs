@@ -109,34 +109,90 @@
 /// producing 62.
 ///
 /// What is the encryption weakness in your XMAS-encrypted list of numbers?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("../input/day_09.txt");
+const DAY: u8 = 9;
 
-pub fn run() {
-    let xmas_data = parse_xmas_data(INPUT);
+/// The length of the preamble, 25 in the puzzle
+pub const PARAMS: &[&str] = &["preamble"];
 
-    let invalid_number = find_invalid_number(&xmas_data, 25).expect("No invalid number found");
-    println!(
-        "The first number to not be a sum of a pair of the previous 25 number is: {}",
-        invalid_number
-    );
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        35\n\
+        20\n\
+        15\n\
+        25\n\
+        47\n\
+        40\n\
+        62\n\
+        55\n\
+        65\n\
+        95\n\
+        102\n\
+        117\n\
+        150\n\
+        182\n\
+        127\n\
+        219\n\
+        299\n\
+        277\n\
+        309\n\
+        576",
+    params: &[("preamble", "5")],
+    answers: &[Some("127"), Some("62")],
+}];
 
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    let xmas_data = parse_xmas_data(input)?;
+    let preamble_size = params.get(DAY, "preamble", 25)?;
+
+    let invalid_number = invalid_number(&xmas_data, preamble_size)?;
+    Ok(Answer::new(
+        format!(
+            "The first number to not be a sum of a pair of the previous {} number is",
+            preamble_size
+        ),
+        invalid_number,
+    ))
+}
+
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let xmas_data = parse_xmas_data(input)?;
+    let preamble_size = params.get(DAY, "preamble", 25)?;
+
+    let invalid_number = invalid_number(&xmas_data, preamble_size)?;
     let encryption_weakness =
-        find_encryption_weakness(&xmas_data, invalid_number).expect("No encryption weakness found");
-    println!(
-        "The encryption weakness of the XMAS-encrypted data is: {}",
-        encryption_weakness
-    )
+        find_encryption_weakness(&xmas_data, invalid_number).ok_or_else(|| {
+            let reason = format!("no contiguous numbers sum to {}", invalid_number);
+            Error::no_answer(DAY, reason)
+        })?;
+    Ok(Answer::new(
+        "The encryption weakness of the XMAS-encrypted data is",
+        encryption_weakness,
+    ))
 }
 
-fn find_invalid_number(xmas_data: &Vec<u64>, preamble_size: usize) -> Option<u64> {
+fn invalid_number(xmas_data: &[u64], preamble_size: usize) -> Result<u64, Error> {
+    find_invalid_number(xmas_data, preamble_size).ok_or_else(|| {
+        let reason = format!(
+            "every number is a sum of a pair of the previous {} numbers",
+            preamble_size
+        );
+        Error::no_answer(DAY, reason)
+    })
+}
+
+pub fn find_invalid_number(xmas_data: &[u64], preamble_size: usize) -> Option<u64> {
     for index in preamble_size..xmas_data.len() {
         let number = xmas_data.get(index).unwrap();
         if !xmas_data[index - preamble_size..index]
             .iter()
             .combinations(2)
-            .any(|vec| *number == vec.into_iter().sum())
+            .any(|vec| *number == vec.into_iter().sum::<u64>())
         {
             return Some(*number);
         }
@@ -144,7 +200,7 @@ fn find_invalid_number(xmas_data: &Vec<u64>, preamble_size: usize) -> Option<u64
     None
 }
 
-fn find_encryption_weakness(xmas_data: &Vec<u64>, invalid_number: u64) -> Option<u64> {
+pub fn find_encryption_weakness(xmas_data: &[u64], invalid_number: u64) -> Option<u64> {
     for from in 0..xmas_data.len() {
         // make sure the sequence is at least 2 long by starting at the from
         let mut sequence_sum = *xmas_data.get(from).unwrap();
@@ -164,27 +220,32 @@ fn find_encryption_weakness(xmas_data: &Vec<u64>, invalid_number: u64) -> Option
     None
 }
 
-fn parse_xmas_data(input: &str) -> Vec<u64> {
-    input
-        .lines()
-        .map(str::parse)
-        .filter_map(Result::ok)
-        .collect()
+pub fn parse_xmas_data(input: &str) -> Result<Vec<u64>, Error> {
+    parse_lines(DAY, input, |line| {
+        line.parse()
+            .map_err(|_| "expected a number of the XMAS data".to_string())
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_xmas_data() {
-        let input = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576";
+        let input = EXAMPLES[0].input;
         let expected_data = vec![
             35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
             576,
         ];
 
-        assert_eq!(parse_xmas_data(input), expected_data);
+        assert_eq!(parse_xmas_data(input), Ok(expected_data));
     }
 
     #[test]
@@ -206,4 +267,20 @@ mod tests {
 
         assert_eq!(find_encryption_weakness(&data, 127), Some(62));
     }
+
+    #[test]
+    fn test_no_answer() {
+        let input = EXAMPLES[0].input;
+        let mut params = Params::new();
+        params.set("preamble", "30");
+
+        let reason = "every number is a sum of a pair of the previous 30 numbers";
+        assert_eq!(part_1(input, &params), Err(Error::no_answer(DAY, reason)));
+        assert_eq!(part_2(input, &params), Err(Error::no_answer(DAY, reason)));
+        params.set("preamble", "2");
+        assert_eq!(
+            part_2("1\n2\n4\n3", &params),
+            Err(Error::no_answer(DAY, "no contiguous numbers sum to 4"))
+        );
+    }
 }
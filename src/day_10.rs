@@ -181,28 +181,92 @@
 ///
 /// What is the total number of distinct ways you can arrange the adapters to
 /// connect the charging outlet to your device?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use itertools::Itertools;
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../input/day_10.txt");
+const DAY: u8 = 10;
 
-pub fn run() {
-    let adapters = parse_jolt_adapters(INPUT);
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+            16\n\
+            10\n\
+            15\n\
+            5\n\
+            1\n\
+            11\n\
+            7\n\
+            19\n\
+            6\n\
+            12\n\
+            4",
+        params: &[],
+        answers: &[Some("35"), Some("8")],
+    },
+    Example {
+        input: "\
+            28\n\
+            33\n\
+            18\n\
+            42\n\
+            31\n\
+            14\n\
+            46\n\
+            20\n\
+            48\n\
+            47\n\
+            24\n\
+            23\n\
+            49\n\
+            45\n\
+            19\n\
+            38\n\
+            39\n\
+            11\n\
+            1\n\
+            32\n\
+            25\n\
+            35\n\
+            8\n\
+            17\n\
+            7\n\
+            9\n\
+            4\n\
+            2\n\
+            34\n\
+            10\n\
+            3",
+        params: &[],
+        answers: &[Some("220"), Some("19208")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let adapters = parse_jolt_adapters(input)?;
 
     let differences = get_differences(adapters);
     let difference_counts = count_differences(&differences);
-    println!(
-        "The number of 1-jolt differences multiplied by the number of 3-jolt differences is: {}",
-        difference_counts.get(&1).unwrap() * difference_counts.get(&3).unwrap()
-    );
+    Ok(Answer::new(
+        "The number of 1-jolt differences multiplied by the number of 3-jolt differences is",
+        difference_counts.get(&1).unwrap() * difference_counts.get(&3).unwrap(),
+    ))
+}
 
-    println!(
-        "The total number of distinct ways adapters can be arranged to connect is: {}",
-        count_distinct_connections(&differences)
-    );
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let adapters = parse_jolt_adapters(input)?;
+
+    let differences = get_differences(adapters);
+    Ok(Answer::new(
+        "The total number of distinct ways adapters can be arranged to connect is",
+        count_distinct_connections(&differences),
+    ))
 }
 
-fn get_differences(mut adapters: Vec<u32>) -> Vec<u32> {
+pub fn get_differences(mut adapters: Vec<u32>) -> Vec<u32> {
     adapters.sort();
     // start with the charging outlet
     let mut differences: Vec<_> = [0]
@@ -216,7 +280,7 @@ fn get_differences(mut adapters: Vec<u32>) -> Vec<u32> {
     differences
 }
 
-fn count_differences(differences: &[u32]) -> HashMap<u32, usize> {
+pub fn count_differences(differences: &[u32]) -> HashMap<u32, usize> {
     differences
         .iter()
         .fold(HashMap::new(), |mut map, &difference| {
@@ -225,10 +289,10 @@ fn count_differences(differences: &[u32]) -> HashMap<u32, usize> {
         })
 }
 
-fn count_distinct_connections(differences: &[u32]) -> u64 {
+pub fn count_distinct_connections(differences: &[u32]) -> u64 {
     differences
         .iter()
-        .group_by(|diff| *diff)
+        .chunk_by(|diff| *diff)
         .into_iter()
         .map(|(k, group)| match k {
             1 => get_possible_connections(group.into_iter().count()),
@@ -238,7 +302,7 @@ fn count_distinct_connections(differences: &[u32]) -> u64 {
         .product()
 }
 
-fn get_possible_connections(n: usize) -> u64 {
+pub fn get_possible_connections(n: usize) -> u64 {
     // The amount of possibilities inside a group with only a difference of 1 is a
     // tribonacci series.
     // in a series that has no difference ( .. 4 .. ) there is only 1 options to reach 4
@@ -260,24 +324,29 @@ fn get_possible_connections(n: usize) -> u64 {
     }
 }
 
-fn parse_jolt_adapters(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .map(str::parse)
-        .filter_map(Result::ok)
-        .collect()
+pub fn parse_jolt_adapters(input: &str) -> Result<Vec<u32>, Error> {
+    parse_lines(DAY, input, |line| {
+        line.parse()
+            .map_err(|_| "expected the joltage rating of an adapter".to_string())
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_jolt_adapters() {
-        let input = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4";
+        let input = EXAMPLES[0].input;
         let expected_adapters = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
 
-        assert_eq!(parse_jolt_adapters(input), expected_adapters);
+        assert_eq!(parse_jolt_adapters(input), Ok(expected_adapters));
     }
 
     #[test]
@@ -237,186 +237,138 @@
 ///
 /// Given the new visibility method and the rule change for occupied seats
 /// becoming empty, once equilibrium is reached, how many seats end up occupied?
-use itertools::Itertools;
-use std::collections::HashMap;
-
-const INPUT: &str = include_str!("../input/day_11.txt");
-
-pub fn run() {
-    let seats = load_seats(INPUT);
-
-    let mut seating = Seating::new(seats.clone(), NeighbourMode::Direct);
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::example::Example;
+use crate::grid::{Grid, Position, Wrap, DIRECTIONS};
+use crate::params::Params;
+
+const DAY: u8 = 11;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        L.LL.LL.LL\n\
+        LLLLLLL.LL\n\
+        L.L.L..L..\n\
+        LLLL.LL.LL\n\
+        L.LL.LL.LL\n\
+        L.LLLLL.LL\n\
+        ..L.L.....\n\
+        LLLLLLLLLL\n\
+        L.LLLLLL.L\n\
+        L.LLLLL.LL",
+    params: &[],
+    answers: &[Some("37"), Some("26")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let seats = load_seats(input)?;
+
+    let mut seating = Seating::new(seats, NeighbourMode::Direct);
 
     // run rounds until a stable solution forms
-    while seating.next_round() {
-        // seating.print();
-    }
+    while seating.next_round() {}
 
     // count the number of occupied seats
-    println!(
-        "After no more seats change the amount of seats that are occupied is: {}",
-        seating.seats_occupied()
-    );
+    Ok(Answer::new(
+        "After no more seats change the amount of seats that are occupied is",
+        seating.seats_occupied(),
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let seats = load_seats(input)?;
 
     // run rounds with the visible neighbours mode until a stable solution forms
-    seating = Seating::new(seats, NeighbourMode::Visible);
-    while seating.next_round() {
-        seating.print();
-    }
+    let mut seating = Seating::new(seats, NeighbourMode::Visible);
+    while seating.next_round() {}
 
-    println!(
-        "With the new mode for neighbours after an equilibrium is reach the amount of seats occupied is: {}",
-        seating.seats_occupied()
-    );
+    Ok(Answer::new(
+        "With the new mode for neighbours after an equilibrium is reach the amount of seats occupied is",
+        seating.seats_occupied(),
+    ))
 }
 
 #[derive(Debug, PartialEq)]
-struct Seating {
-    seats: HashMap<Location, Seat>,
-    bounds: Bounds,
+pub struct Seating {
+    seats: Seats,
     neighbour_mode: NeighbourMode,
 }
 
-type Location = (i32, i32);
 #[derive(Debug, PartialEq, Clone)]
-enum Seat {
+pub enum Seat {
     Empty,
     Occupied,
 }
-type Seats = HashMap<Location, Seat>;
-
-#[derive(Debug, PartialEq)]
-struct Bounds {
-    x: MinMax,
-    y: MinMax,
-}
+/// The seating area, the positions without a seat are floor
+pub type Seats = Grid<Option<Seat>>;
 
 #[derive(Debug, PartialEq)]
-struct MinMax {
-    min: i32,
-    max: i32,
-}
-
-#[derive(Debug, PartialEq)]
-enum NeighbourMode {
+pub enum NeighbourMode {
     Direct,
     Visible,
 }
 
 impl Seating {
-    fn new(seats: Seats, neighbour_mode: NeighbourMode) -> Seating {
-        let bounds = Seating::get_bounds(&seats);
+    pub fn new(seats: Seats, neighbour_mode: NeighbourMode) -> Seating {
         Seating {
             seats,
-            bounds,
             neighbour_mode,
         }
     }
 
-    fn get_bounds(seats: &Seats) -> Bounds {
-        let (&x_min, &x_max) = seats.keys().map(|(x, _)| x).minmax().into_option().unwrap();
-        let (&y_min, &y_max) = seats.keys().map(|(_, y)| y).minmax().into_option().unwrap();
-        Bounds {
-            x: MinMax {
-                min: x_min,
-                max: x_max,
-            },
-            y: MinMax {
-                min: y_min,
-                max: y_max,
-            },
-        }
+    pub fn render(&self) -> String {
+        self.seats.render(|seat| match seat {
+            Some(Seat::Empty) => 'L',
+            Some(Seat::Occupied) => '#',
+            None => '.',
+        })
     }
 
-    fn print(&self) {
-        // clear the screen
-        print!("\x1B[2J\x1B[1;1H");
-
-        for x in self.bounds.x.min..=self.bounds.x.max {
-            for y in self.bounds.y.min..=self.bounds.y.max {
-                match self.seats.get(&(x, y)) {
-                    Some(Seat::Empty) => print!("L"),
-                    Some(Seat::Occupied) => print!("#"),
-                    None => print!("."),
-                }
-            }
-            print!("\n");
-        }
-        print!("\n\n");
+    fn is_occupied(&self, location: Position) -> bool {
+        self.seats[location] == Some(Seat::Occupied)
     }
 
-    fn direct_neighbours(&self, &(x, y): &Location) -> Vec<Location> {
-        [
-            (x - 1, y - 1),
-            (x - 1, y),
-            (x - 1, y + 1),
-            (x, y - 1),
-            (x, y + 1),
-            (x + 1, y - 1),
-            (x + 1, y),
-            (x + 1, y + 1),
-        ]
-        .iter()
-        .filter(|location| self.seats.contains_key(&location))
-        .cloned()
-        .collect()
+    fn direct_neighbours(&self, location: Position) -> Vec<Position> {
+        self.seats
+            .neighbours(location)
+            .filter(|&neighbour| self.seats[neighbour].is_some())
+            .collect()
     }
 
-    fn visible_neighbours(&self, location: &Location) -> Vec<Location> {
-        [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ]
-        .iter()
-        .filter_map(|direction| self.first_seat_in_direction(location, direction))
-        .collect()
+    fn visible_neighbours(&self, location: Position) -> Vec<Position> {
+        DIRECTIONS
+            .iter()
+            .filter_map(|&direction| self.first_seat_in_direction(location, direction))
+            .collect()
     }
 
     fn first_seat_in_direction(
         &self,
-        location: &Location,
-        &(d_x, d_y): &Location,
-    ) -> Option<Location> {
-        let (mut x, mut y) = location;
-        while self.bounds.x.min <= x
-            && x <= self.bounds.x.max
-            && self.bounds.y.min <= y
-            && y <= self.bounds.y.max
-        {
-            x += d_x;
-            y += d_y;
-            if self.seats.contains_key(&(x, y)) {
-                return Some((x, y));
+        mut location: Position,
+        direction: (isize, isize),
+    ) -> Option<Position> {
+        while let Some(next) = self.seats.offset(location, direction, Wrap::None) {
+            if self.seats[next].is_some() {
+                return Some(next);
             }
+            location = next;
         }
         None
     }
 
-    fn get_neighbour_counts(&self) -> HashMap<Location, usize> {
-        // count all the neighbours for each seating location
-        self.seats
-            .iter()
-            .filter_map(|(location, seat)| match seat {
-                Seat::Occupied => Some(location),
-                Seat::Empty => None,
-            })
-            .flat_map(|location| match &self.neighbour_mode {
-                NeighbourMode::Direct => self.direct_neighbours(location),
-                NeighbourMode::Visible => self.visible_neighbours(location),
-            })
-            .fold(HashMap::new(), |mut map, neighbour| {
-                *map.entry(neighbour).or_insert(0) += 1;
-                map
-            })
+    fn count_occupied_neighbours(&self, location: Position) -> usize {
+        let neighbours = match &self.neighbour_mode {
+            NeighbourMode::Direct => self.direct_neighbours(location),
+            NeighbourMode::Visible => self.visible_neighbours(location),
+        };
+        neighbours
+            .into_iter()
+            .filter(|&neighbour| self.is_occupied(neighbour))
+            .count()
     }
 
-    fn next_round(&mut self) -> bool {
+    pub fn next_round(&mut self) -> bool {
         // For Direct NeighbourMode:
         // - If a seat is empty (L) and there are no occupied seats adjacent to it,
         //   the seat becomes occupied.
@@ -432,328 +384,81 @@ impl Seating {
         // than four or more from the previous rules). The other rules still apply:
         // empty seats that see no occupied seats become occupied, seats matching no
         // rule don't change, and floor never changes.
-        let neighbour_counts = self.get_neighbour_counts();
-        let new_seats = self
-            .seats
-            .iter()
-            .map(|(&location, seat)| {
-                let new_seat = match seat {
-                    &Seat::Empty => match neighbour_counts.get(&location) {
-                        Some(&n) if n > 0 => Seat::Empty,
-                        _ => Seat::Occupied,
-                    },
-                    &Seat::Occupied => {
-                        match (neighbour_counts.get(&location), &self.neighbour_mode) {
-                            (Some(&n), NeighbourMode::Direct) if n >= 4 => Seat::Empty,
-                            (Some(&n), NeighbourMode::Visible) if n >= 5 => Seat::Empty,
-                            _ => Seat::Occupied,
-                        }
-                    }
-                };
-                (location, new_seat)
+        let new_seats = self.seats.map(|location, seat| {
+            let seat = seat.as_ref()?;
+            let occupied = self.count_occupied_neighbours(location);
+            Some(match (seat, &self.neighbour_mode) {
+                (Seat::Empty, _) if occupied > 0 => Seat::Empty,
+                (Seat::Empty, _) => Seat::Occupied,
+                (Seat::Occupied, NeighbourMode::Direct) if occupied >= 4 => Seat::Empty,
+                (Seat::Occupied, NeighbourMode::Visible) if occupied >= 5 => Seat::Empty,
+                (Seat::Occupied, _) => Seat::Occupied,
             })
-            .collect();
+        });
 
         if new_seats == self.seats {
             return false;
         }
         self.seats = new_seats;
-        return true;
+        true
     }
 
-    fn seats_occupied(&self) -> usize {
+    pub fn seats_occupied(&self) -> usize {
         self.seats
-            .values()
-            .filter(|seat| seat == &&Seat::Occupied)
+            .iter()
+            .filter(|(_, seat)| seat == &&Some(Seat::Occupied))
             .count()
     }
 }
 
-fn load_seats(input: &str) -> Seats {
-    input
-        .lines()
-        .enumerate()
-        .map(|(x, line)| {
-            line.chars().enumerate().filter_map(move |(y, c)| match c {
-                'L' => Some(((x as i32, y as i32), Seat::Empty)),
-                _ => None,
-            })
-        })
-        .flatten()
-        .collect()
+pub fn load_seats(input: &str) -> Result<Seats, Error> {
+    Grid::parse(DAY, input, |c| match c {
+        'L' => Ok(Some(Seat::Empty)),
+        '.' => Ok(None),
+        _ => Err(format!("unknown position {}, expected L or .", c)),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_seats() {
-        let input = "\
-            L.LL.LL.LL\n\
-            LLLLLLL.LL\n\
-            L.L.L..L..\n\
-            LLLL.LL.LL\n\
-            L.LL.LL.LL\n\
-            L.LLLLL.LL\n\
-            ..L.L.....\n\
-            LLLLLLLLLL\n\
-            L.LLLLLL.L\n\
-            L.LLLLL.LL";
+        let input = EXAMPLES[0].input;
+        let seats = load_seats(input).unwrap();
 
-        let mut expected_seats = HashMap::new();
-        // L.LL.LL.LL
-        expected_seats.insert((0, 0), Seat::Empty);
-        expected_seats.insert((0, 2), Seat::Empty);
-        expected_seats.insert((0, 3), Seat::Empty);
-        expected_seats.insert((0, 5), Seat::Empty);
-        expected_seats.insert((0, 6), Seat::Empty);
-        expected_seats.insert((0, 8), Seat::Empty);
-        expected_seats.insert((0, 9), Seat::Empty);
-        // LLLLLLL.LL
-        expected_seats.insert((1, 0), Seat::Empty);
-        expected_seats.insert((1, 1), Seat::Empty);
-        expected_seats.insert((1, 2), Seat::Empty);
-        expected_seats.insert((1, 3), Seat::Empty);
-        expected_seats.insert((1, 4), Seat::Empty);
-        expected_seats.insert((1, 5), Seat::Empty);
-        expected_seats.insert((1, 6), Seat::Empty);
-        expected_seats.insert((1, 8), Seat::Empty);
-        expected_seats.insert((1, 9), Seat::Empty);
-        // L.L.L..L..
-        expected_seats.insert((2, 0), Seat::Empty);
-        expected_seats.insert((2, 2), Seat::Empty);
-        expected_seats.insert((2, 4), Seat::Empty);
-        expected_seats.insert((2, 7), Seat::Empty);
-        // LLLL.LL.LL
-        expected_seats.insert((3, 0), Seat::Empty);
-        expected_seats.insert((3, 1), Seat::Empty);
-        expected_seats.insert((3, 2), Seat::Empty);
-        expected_seats.insert((3, 3), Seat::Empty);
-        expected_seats.insert((3, 5), Seat::Empty);
-        expected_seats.insert((3, 6), Seat::Empty);
-        expected_seats.insert((3, 8), Seat::Empty);
-        expected_seats.insert((3, 9), Seat::Empty);
+        assert_eq!((seats.width(), seats.height()), (10, 10));
         // L.LL.LL.LL
-        expected_seats.insert((4, 0), Seat::Empty);
-        expected_seats.insert((4, 2), Seat::Empty);
-        expected_seats.insert((4, 3), Seat::Empty);
-        expected_seats.insert((4, 5), Seat::Empty);
-        expected_seats.insert((4, 6), Seat::Empty);
-        expected_seats.insert((4, 8), Seat::Empty);
-        expected_seats.insert((4, 9), Seat::Empty);
-        // L.LLLLL.LL
-        expected_seats.insert((5, 0), Seat::Empty);
-        expected_seats.insert((5, 2), Seat::Empty);
-        expected_seats.insert((5, 3), Seat::Empty);
-        expected_seats.insert((5, 4), Seat::Empty);
-        expected_seats.insert((5, 5), Seat::Empty);
-        expected_seats.insert((5, 6), Seat::Empty);
-        expected_seats.insert((5, 8), Seat::Empty);
-        expected_seats.insert((5, 9), Seat::Empty);
+        assert_eq!(seats[(0, 0)], Some(Seat::Empty));
+        assert_eq!(seats[(0, 1)], None);
         // ..L.L.....
-        expected_seats.insert((6, 2), Seat::Empty);
-        expected_seats.insert((6, 4), Seat::Empty);
-        // LLLLLLLLLL
-        expected_seats.insert((7, 0), Seat::Empty);
-        expected_seats.insert((7, 1), Seat::Empty);
-        expected_seats.insert((7, 2), Seat::Empty);
-        expected_seats.insert((7, 3), Seat::Empty);
-        expected_seats.insert((7, 4), Seat::Empty);
-        expected_seats.insert((7, 5), Seat::Empty);
-        expected_seats.insert((7, 6), Seat::Empty);
-        expected_seats.insert((7, 7), Seat::Empty);
-        expected_seats.insert((7, 8), Seat::Empty);
-        expected_seats.insert((7, 9), Seat::Empty);
-        // L.LLLLLL.L
-        expected_seats.insert((8, 0), Seat::Empty);
-        expected_seats.insert((8, 2), Seat::Empty);
-        expected_seats.insert((8, 3), Seat::Empty);
-        expected_seats.insert((8, 4), Seat::Empty);
-        expected_seats.insert((8, 5), Seat::Empty);
-        expected_seats.insert((8, 6), Seat::Empty);
-        expected_seats.insert((8, 7), Seat::Empty);
-        expected_seats.insert((8, 9), Seat::Empty);
-        // L.LLLLL.LL
-        expected_seats.insert((9, 0), Seat::Empty);
-        expected_seats.insert((9, 2), Seat::Empty);
-        expected_seats.insert((9, 3), Seat::Empty);
-        expected_seats.insert((9, 4), Seat::Empty);
-        expected_seats.insert((9, 5), Seat::Empty);
-        expected_seats.insert((9, 6), Seat::Empty);
-        expected_seats.insert((9, 8), Seat::Empty);
-        expected_seats.insert((9, 9), Seat::Empty);
-
-        assert_eq!(load_seats(input), expected_seats);
+        assert_eq!(seats[(6, 2)], Some(Seat::Empty));
+        assert_eq!(seats.iter().filter(|(_, seat)| seat.is_some()).count(), 71);
+        assert_eq!(Seating::new(seats, NeighbourMode::Direct).render(), input);
+        assert!(load_seats("L.L\nL#L").is_err());
     }
 
     #[test]
     fn test_next_round_direct_neighbours() {
-        let mut seats = HashMap::new();
-        // L.LL.LL.LL
-        seats.insert((0, 0), Seat::Empty);
-        seats.insert((0, 2), Seat::Empty);
-        seats.insert((0, 3), Seat::Empty);
-        seats.insert((0, 5), Seat::Empty);
-        seats.insert((0, 6), Seat::Empty);
-        seats.insert((0, 8), Seat::Empty);
-        seats.insert((0, 9), Seat::Empty);
-        // LLLLLLL.LL
-        seats.insert((1, 0), Seat::Empty);
-        seats.insert((1, 1), Seat::Empty);
-        seats.insert((1, 2), Seat::Empty);
-        seats.insert((1, 3), Seat::Empty);
-        seats.insert((1, 4), Seat::Empty);
-        seats.insert((1, 5), Seat::Empty);
-        seats.insert((1, 6), Seat::Empty);
-        seats.insert((1, 8), Seat::Empty);
-        seats.insert((1, 9), Seat::Empty);
-        // L.L.L..L..
-        seats.insert((2, 0), Seat::Empty);
-        seats.insert((2, 2), Seat::Empty);
-        seats.insert((2, 4), Seat::Empty);
-        seats.insert((2, 7), Seat::Empty);
-        // LLLL.LL.LL
-        seats.insert((3, 0), Seat::Empty);
-        seats.insert((3, 1), Seat::Empty);
-        seats.insert((3, 2), Seat::Empty);
-        seats.insert((3, 3), Seat::Empty);
-        seats.insert((3, 5), Seat::Empty);
-        seats.insert((3, 6), Seat::Empty);
-        seats.insert((3, 8), Seat::Empty);
-        seats.insert((3, 9), Seat::Empty);
-        // L.LL.LL.LL
-        seats.insert((4, 0), Seat::Empty);
-        seats.insert((4, 2), Seat::Empty);
-        seats.insert((4, 3), Seat::Empty);
-        seats.insert((4, 5), Seat::Empty);
-        seats.insert((4, 6), Seat::Empty);
-        seats.insert((4, 8), Seat::Empty);
-        seats.insert((4, 9), Seat::Empty);
-        // L.LLLLL.LL
-        seats.insert((5, 0), Seat::Empty);
-        seats.insert((5, 2), Seat::Empty);
-        seats.insert((5, 3), Seat::Empty);
-        seats.insert((5, 4), Seat::Empty);
-        seats.insert((5, 5), Seat::Empty);
-        seats.insert((5, 6), Seat::Empty);
-        seats.insert((5, 8), Seat::Empty);
-        seats.insert((5, 9), Seat::Empty);
-        // ..L.L.....
-        seats.insert((6, 2), Seat::Empty);
-        seats.insert((6, 4), Seat::Empty);
-        // LLLLLLLLLL
-        seats.insert((7, 0), Seat::Empty);
-        seats.insert((7, 1), Seat::Empty);
-        seats.insert((7, 2), Seat::Empty);
-        seats.insert((7, 3), Seat::Empty);
-        seats.insert((7, 4), Seat::Empty);
-        seats.insert((7, 5), Seat::Empty);
-        seats.insert((7, 6), Seat::Empty);
-        seats.insert((7, 7), Seat::Empty);
-        seats.insert((7, 8), Seat::Empty);
-        seats.insert((7, 9), Seat::Empty);
-        // L.LLLLLL.L
-        seats.insert((8, 0), Seat::Empty);
-        seats.insert((8, 2), Seat::Empty);
-        seats.insert((8, 3), Seat::Empty);
-        seats.insert((8, 4), Seat::Empty);
-        seats.insert((8, 5), Seat::Empty);
-        seats.insert((8, 6), Seat::Empty);
-        seats.insert((8, 7), Seat::Empty);
-        seats.insert((8, 9), Seat::Empty);
-        // L.LLLLL.LL
-        seats.insert((9, 0), Seat::Empty);
-        seats.insert((9, 2), Seat::Empty);
-        seats.insert((9, 3), Seat::Empty);
-        seats.insert((9, 4), Seat::Empty);
-        seats.insert((9, 5), Seat::Empty);
-        seats.insert((9, 6), Seat::Empty);
-        seats.insert((9, 8), Seat::Empty);
-        seats.insert((9, 9), Seat::Empty);
-
-        let mut stabilized = HashMap::new();
-        // #.#L.L#.##
-        stabilized.insert((0, 0), Seat::Occupied);
-        stabilized.insert((0, 2), Seat::Occupied);
-        stabilized.insert((0, 3), Seat::Empty);
-        stabilized.insert((0, 5), Seat::Empty);
-        stabilized.insert((0, 6), Seat::Occupied);
-        stabilized.insert((0, 8), Seat::Occupied);
-        stabilized.insert((0, 9), Seat::Occupied);
-        // #LLL#LL.L#
-        stabilized.insert((1, 0), Seat::Occupied);
-        stabilized.insert((1, 1), Seat::Empty);
-        stabilized.insert((1, 2), Seat::Empty);
-        stabilized.insert((1, 3), Seat::Empty);
-        stabilized.insert((1, 4), Seat::Occupied);
-        stabilized.insert((1, 5), Seat::Empty);
-        stabilized.insert((1, 6), Seat::Empty);
-        stabilized.insert((1, 8), Seat::Empty);
-        stabilized.insert((1, 9), Seat::Occupied);
-        // L.#.L..#..
-        stabilized.insert((2, 0), Seat::Empty);
-        stabilized.insert((2, 2), Seat::Occupied);
-        stabilized.insert((2, 4), Seat::Empty);
-        stabilized.insert((2, 7), Seat::Occupied);
-        // #L##.##.L#
-        stabilized.insert((3, 0), Seat::Occupied);
-        stabilized.insert((3, 1), Seat::Empty);
-        stabilized.insert((3, 2), Seat::Occupied);
-        stabilized.insert((3, 3), Seat::Occupied);
-        stabilized.insert((3, 5), Seat::Occupied);
-        stabilized.insert((3, 6), Seat::Occupied);
-        stabilized.insert((3, 8), Seat::Empty);
-        stabilized.insert((3, 9), Seat::Occupied);
-        // #.#L.LL.LL
-        stabilized.insert((4, 0), Seat::Occupied);
-        stabilized.insert((4, 2), Seat::Occupied);
-        stabilized.insert((4, 3), Seat::Empty);
-        stabilized.insert((4, 5), Seat::Empty);
-        stabilized.insert((4, 6), Seat::Empty);
-        stabilized.insert((4, 8), Seat::Empty);
-        stabilized.insert((4, 9), Seat::Empty);
-        // #.#L#L#.##
-        stabilized.insert((5, 0), Seat::Occupied);
-        stabilized.insert((5, 2), Seat::Occupied);
-        stabilized.insert((5, 3), Seat::Empty);
-        stabilized.insert((5, 4), Seat::Occupied);
-        stabilized.insert((5, 5), Seat::Empty);
-        stabilized.insert((5, 6), Seat::Occupied);
-        stabilized.insert((5, 8), Seat::Occupied);
-        stabilized.insert((5, 9), Seat::Occupied);
-        // ..L.L.....
-        stabilized.insert((6, 2), Seat::Empty);
-        stabilized.insert((6, 4), Seat::Empty);
-        // #L#L##L#L#
-        stabilized.insert((7, 0), Seat::Occupied);
-        stabilized.insert((7, 1), Seat::Empty);
-        stabilized.insert((7, 2), Seat::Occupied);
-        stabilized.insert((7, 3), Seat::Empty);
-        stabilized.insert((7, 4), Seat::Occupied);
-        stabilized.insert((7, 5), Seat::Occupied);
-        stabilized.insert((7, 6), Seat::Empty);
-        stabilized.insert((7, 7), Seat::Occupied);
-        stabilized.insert((7, 8), Seat::Empty);
-        stabilized.insert((7, 9), Seat::Occupied);
-        // #.LLLLLL.L
-        stabilized.insert((8, 0), Seat::Occupied);
-        stabilized.insert((8, 2), Seat::Empty);
-        stabilized.insert((8, 3), Seat::Empty);
-        stabilized.insert((8, 4), Seat::Empty);
-        stabilized.insert((8, 5), Seat::Empty);
-        stabilized.insert((8, 6), Seat::Empty);
-        stabilized.insert((8, 7), Seat::Empty);
-        stabilized.insert((8, 9), Seat::Empty);
-        // #.#L#L#.##
-        stabilized.insert((9, 0), Seat::Occupied);
-        stabilized.insert((9, 2), Seat::Occupied);
-        stabilized.insert((9, 3), Seat::Empty);
-        stabilized.insert((9, 4), Seat::Occupied);
-        stabilized.insert((9, 5), Seat::Empty);
-        stabilized.insert((9, 6), Seat::Occupied);
-        stabilized.insert((9, 8), Seat::Occupied);
-        stabilized.insert((9, 9), Seat::Occupied);
+        let seats = load_seats(EXAMPLES[0].input).unwrap();
+        let stabilized = "\
+            #.#L.L#.##\n\
+            #LLL#LL.L#\n\
+            L.#.L..#..\n\
+            #L##.##.L#\n\
+            #.#L.LL.LL\n\
+            #.#L#L#.##\n\
+            ..L.L.....\n\
+            #L#L##L#L#\n\
+            #.LLLLLL.L\n\
+            #.#L#L#.##";
 
         let mut seating = Seating::new(seats, NeighbourMode::Direct);
         for _ in 0..5 {
@@ -763,176 +468,23 @@ mod tests {
         // 6th round should not
         assert!(!seating.next_round());
         // and seating should be the stabilized configuration
-        assert_eq!(seating.seats, stabilized);
+        assert_eq!(seating.render(), stabilized);
     }
 
     #[test]
     fn test_next_round_visible_neighbours() {
-        let mut seats = HashMap::new();
-        // L.LL.LL.LL
-        seats.insert((0, 0), Seat::Empty);
-        seats.insert((0, 2), Seat::Empty);
-        seats.insert((0, 3), Seat::Empty);
-        seats.insert((0, 5), Seat::Empty);
-        seats.insert((0, 6), Seat::Empty);
-        seats.insert((0, 8), Seat::Empty);
-        seats.insert((0, 9), Seat::Empty);
-        // LLLLLLL.LL
-        seats.insert((1, 0), Seat::Empty);
-        seats.insert((1, 1), Seat::Empty);
-        seats.insert((1, 2), Seat::Empty);
-        seats.insert((1, 3), Seat::Empty);
-        seats.insert((1, 4), Seat::Empty);
-        seats.insert((1, 5), Seat::Empty);
-        seats.insert((1, 6), Seat::Empty);
-        seats.insert((1, 8), Seat::Empty);
-        seats.insert((1, 9), Seat::Empty);
-        // L.L.L..L..
-        seats.insert((2, 0), Seat::Empty);
-        seats.insert((2, 2), Seat::Empty);
-        seats.insert((2, 4), Seat::Empty);
-        seats.insert((2, 7), Seat::Empty);
-        // LLLL.LL.LL
-        seats.insert((3, 0), Seat::Empty);
-        seats.insert((3, 1), Seat::Empty);
-        seats.insert((3, 2), Seat::Empty);
-        seats.insert((3, 3), Seat::Empty);
-        seats.insert((3, 5), Seat::Empty);
-        seats.insert((3, 6), Seat::Empty);
-        seats.insert((3, 8), Seat::Empty);
-        seats.insert((3, 9), Seat::Empty);
-        // L.LL.LL.LL
-        seats.insert((4, 0), Seat::Empty);
-        seats.insert((4, 2), Seat::Empty);
-        seats.insert((4, 3), Seat::Empty);
-        seats.insert((4, 5), Seat::Empty);
-        seats.insert((4, 6), Seat::Empty);
-        seats.insert((4, 8), Seat::Empty);
-        seats.insert((4, 9), Seat::Empty);
-        // L.LLLLL.LL
-        seats.insert((5, 0), Seat::Empty);
-        seats.insert((5, 2), Seat::Empty);
-        seats.insert((5, 3), Seat::Empty);
-        seats.insert((5, 4), Seat::Empty);
-        seats.insert((5, 5), Seat::Empty);
-        seats.insert((5, 6), Seat::Empty);
-        seats.insert((5, 8), Seat::Empty);
-        seats.insert((5, 9), Seat::Empty);
-        // ..L.L.....
-        seats.insert((6, 2), Seat::Empty);
-        seats.insert((6, 4), Seat::Empty);
-        // LLLLLLLLLL
-        seats.insert((7, 0), Seat::Empty);
-        seats.insert((7, 1), Seat::Empty);
-        seats.insert((7, 2), Seat::Empty);
-        seats.insert((7, 3), Seat::Empty);
-        seats.insert((7, 4), Seat::Empty);
-        seats.insert((7, 5), Seat::Empty);
-        seats.insert((7, 6), Seat::Empty);
-        seats.insert((7, 7), Seat::Empty);
-        seats.insert((7, 8), Seat::Empty);
-        seats.insert((7, 9), Seat::Empty);
-        // L.LLLLLL.L
-        seats.insert((8, 0), Seat::Empty);
-        seats.insert((8, 2), Seat::Empty);
-        seats.insert((8, 3), Seat::Empty);
-        seats.insert((8, 4), Seat::Empty);
-        seats.insert((8, 5), Seat::Empty);
-        seats.insert((8, 6), Seat::Empty);
-        seats.insert((8, 7), Seat::Empty);
-        seats.insert((8, 9), Seat::Empty);
-        // L.LLLLL.LL
-        seats.insert((9, 0), Seat::Empty);
-        seats.insert((9, 2), Seat::Empty);
-        seats.insert((9, 3), Seat::Empty);
-        seats.insert((9, 4), Seat::Empty);
-        seats.insert((9, 5), Seat::Empty);
-        seats.insert((9, 6), Seat::Empty);
-        seats.insert((9, 8), Seat::Empty);
-        seats.insert((9, 9), Seat::Empty);
-
-        let mut stabilized = HashMap::new();
-        // #.L#.L#.L#
-        stabilized.insert((0, 0), Seat::Occupied);
-        stabilized.insert((0, 2), Seat::Empty);
-        stabilized.insert((0, 3), Seat::Occupied);
-        stabilized.insert((0, 5), Seat::Empty);
-        stabilized.insert((0, 6), Seat::Occupied);
-        stabilized.insert((0, 8), Seat::Empty);
-        stabilized.insert((0, 9), Seat::Occupied);
-        // #LLLLLL.LL
-        stabilized.insert((1, 0), Seat::Occupied);
-        stabilized.insert((1, 1), Seat::Empty);
-        stabilized.insert((1, 2), Seat::Empty);
-        stabilized.insert((1, 3), Seat::Empty);
-        stabilized.insert((1, 4), Seat::Empty);
-        stabilized.insert((1, 5), Seat::Empty);
-        stabilized.insert((1, 6), Seat::Empty);
-        stabilized.insert((1, 8), Seat::Empty);
-        stabilized.insert((1, 9), Seat::Empty);
-        // L.L.L..#..
-        stabilized.insert((2, 0), Seat::Empty);
-        stabilized.insert((2, 2), Seat::Empty);
-        stabilized.insert((2, 4), Seat::Empty);
-        stabilized.insert((2, 7), Seat::Occupied);
-        // ##L#.#L.L#
-        stabilized.insert((3, 0), Seat::Occupied);
-        stabilized.insert((3, 1), Seat::Occupied);
-        stabilized.insert((3, 2), Seat::Empty);
-        stabilized.insert((3, 3), Seat::Occupied);
-        stabilized.insert((3, 5), Seat::Occupied);
-        stabilized.insert((3, 6), Seat::Empty);
-        stabilized.insert((3, 8), Seat::Empty);
-        stabilized.insert((3, 9), Seat::Occupied);
-        // L.L#.LL.L#
-        stabilized.insert((4, 0), Seat::Empty);
-        stabilized.insert((4, 2), Seat::Empty);
-        stabilized.insert((4, 3), Seat::Occupied);
-        stabilized.insert((4, 5), Seat::Empty);
-        stabilized.insert((4, 6), Seat::Empty);
-        stabilized.insert((4, 8), Seat::Empty);
-        stabilized.insert((4, 9), Seat::Occupied);
-        // #.LLLL#.LL
-        stabilized.insert((5, 0), Seat::Occupied);
-        stabilized.insert((5, 2), Seat::Empty);
-        stabilized.insert((5, 3), Seat::Empty);
-        stabilized.insert((5, 4), Seat::Empty);
-        stabilized.insert((5, 5), Seat::Empty);
-        stabilized.insert((5, 6), Seat::Occupied);
-        stabilized.insert((5, 8), Seat::Empty);
-        stabilized.insert((5, 9), Seat::Empty);
-        // ..#.L.....
-        stabilized.insert((6, 2), Seat::Occupied);
-        stabilized.insert((6, 4), Seat::Empty);
-        // LLL###LLL#
-        stabilized.insert((7, 0), Seat::Empty);
-        stabilized.insert((7, 1), Seat::Empty);
-        stabilized.insert((7, 2), Seat::Empty);
-        stabilized.insert((7, 3), Seat::Occupied);
-        stabilized.insert((7, 4), Seat::Occupied);
-        stabilized.insert((7, 5), Seat::Occupied);
-        stabilized.insert((7, 6), Seat::Empty);
-        stabilized.insert((7, 7), Seat::Empty);
-        stabilized.insert((7, 8), Seat::Empty);
-        stabilized.insert((7, 9), Seat::Occupied);
-        // #.LLLLL#.L
-        stabilized.insert((8, 0), Seat::Occupied);
-        stabilized.insert((8, 2), Seat::Empty);
-        stabilized.insert((8, 3), Seat::Empty);
-        stabilized.insert((8, 4), Seat::Empty);
-        stabilized.insert((8, 5), Seat::Empty);
-        stabilized.insert((8, 6), Seat::Empty);
-        stabilized.insert((8, 7), Seat::Occupied);
-        stabilized.insert((8, 9), Seat::Empty);
-        // #.L#LL#.L#
-        stabilized.insert((9, 0), Seat::Occupied);
-        stabilized.insert((9, 2), Seat::Empty);
-        stabilized.insert((9, 3), Seat::Occupied);
-        stabilized.insert((9, 4), Seat::Empty);
-        stabilized.insert((9, 5), Seat::Empty);
-        stabilized.insert((9, 6), Seat::Occupied);
-        stabilized.insert((9, 8), Seat::Empty);
-        stabilized.insert((9, 9), Seat::Occupied);
+        let seats = load_seats(EXAMPLES[0].input).unwrap();
+        let stabilized = "\
+            #.L#.L#.L#\n\
+            #LLLLLL.LL\n\
+            L.L.L..#..\n\
+            ##L#.#L.L#\n\
+            L.L#.LL.L#\n\
+            #.LLLL#.LL\n\
+            ..#.L.....\n\
+            LLL###LLL#\n\
+            #.LLLLL#.L\n\
+            #.L#LL#.L#";
 
         let mut seating = Seating::new(seats, NeighbourMode::Visible);
         for _ in 0..6 {
@@ -942,6 +494,6 @@ mod tests {
         // 7th round should not
         assert!(!seating.next_round());
         // and seating should be the stabilized configuration
-        assert_eq!(seating.seats, stabilized);
+        assert_eq!(seating.render(), stabilized);
     }
 }
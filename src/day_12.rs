@@ -98,53 +98,78 @@
 ///
 /// Figure out where the navigation instructions actually lead. What is the
 /// Manhattan distance between that location and the ship's starting position?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use num;
 use num_derive::{FromPrimitive, ToPrimitive};
 use regex::Regex;
 
-const INPUT: &str = include_str!("../input/day_12.txt");
+const DAY: u8 = 12;
 
-pub fn run() {
-    let instructions = parse_instructions(INPUT);
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        F10\n\
+        N3\n\
+        F7\n\
+        R90\n\
+        F11",
+    params: &[],
+    answers: &[Some("25"), Some("286")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let instructions = parse_instructions(input)?;
 
     // navigate the ship according to the instructions
     let mut ship = Ship::new();
     ship.navigate_multiple(&instructions);
 
-    println!(
-        "The Manhattan distance between the ship's destination and the starting position is: {}",
-        ship.location.0.abs() + ship.location.1.abs()
-    );
+    Ok(Answer::new(
+        "The Manhattan distance between the ship's destination and the starting position is",
+        ship.location.0.abs() + ship.location.1.abs(),
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let instructions = parse_instructions(input)?;
 
-    // create a new ship to navigate it by waypoint
-    ship = Ship::new();
+    // navigate the ship by waypoint
+    let mut ship = Ship::new();
     ship.navigate_by_waypoint_multiple(&instructions);
 
-    println!(
-        "The Manhattan distance between the ship's actual destination and the starting position is: {}",
-        ship.location.0.abs() + ship.location.1.abs()
-    );
+    Ok(Answer::new(
+        "The Manhattan distance between the ship's actual destination and the starting position is",
+        ship.location.0.abs() + ship.location.1.abs(),
+    ))
 }
 
 #[derive(Debug, PartialEq)]
-struct Ship {
-    facing: Direction,
-    location: Location,
-    waypoint: Location,
+pub struct Ship {
+    pub facing: Direction,
+    pub location: Location,
+    pub waypoint: Location,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive, ToPrimitive)]
-enum Direction {
+pub enum Direction {
     North,
     East,
     South,
     West,
 }
 
-type Location = (i32, i32);
+pub type Location = (i32, i32);
+
+impl Default for Ship {
+    fn default() -> Ship {
+        Ship::new()
+    }
+}
 
 impl Ship {
-    fn new() -> Ship {
+    pub fn new() -> Ship {
         Ship {
             facing: Direction::East,
             location: (0, 0),
@@ -152,7 +177,7 @@ impl Ship {
         }
     }
 
-    fn navigate(&mut self, instruction: &Instruction) {
+    pub fn navigate(&mut self, instruction: &Instruction) {
         match instruction.action {
             Action::Left | Action::Right => self.turn(&instruction.action, instruction.value),
             Action::Forward => self.move_in_direction(self.facing, instruction.value),
@@ -162,7 +187,7 @@ impl Ship {
         };
     }
 
-    fn navigate_by_waypoint(&mut self, instruction: &Instruction) {
+    pub fn navigate_by_waypoint(&mut self, instruction: &Instruction) {
         match instruction.action {
             Action::Left | Action::Right => {
                 self.rotate_waypoint(&instruction.action, instruction.value)
@@ -239,13 +264,13 @@ impl Ship {
         self.location = (x + (w_x * times), y + (w_y * times));
     }
 
-    fn navigate_multiple(&mut self, instructions: &[Instruction]) {
+    pub fn navigate_multiple(&mut self, instructions: &[Instruction]) {
         for instruction in instructions.iter() {
             self.navigate(instruction);
         }
     }
 
-    fn navigate_by_waypoint_multiple(&mut self, instructions: &[Instruction]) {
+    pub fn navigate_by_waypoint_multiple(&mut self, instructions: &[Instruction]) {
         for instruction in instructions.iter() {
             self.navigate_by_waypoint(instruction);
         }
@@ -253,13 +278,13 @@ impl Ship {
 }
 
 #[derive(Debug, PartialEq)]
-struct Instruction {
-    action: Action,
-    value: i32,
+pub struct Instruction {
+    pub action: Action,
+    pub value: i32,
 }
 
 #[derive(Debug, PartialEq)]
-enum Action {
+pub enum Action {
     North,
     South,
     East,
@@ -269,40 +294,52 @@ enum Action {
     Forward,
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    input.lines().filter_map(convert_to_instruction).collect()
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, Error> {
+    parse_lines(DAY, input, convert_to_instruction)
 }
 
-fn convert_to_instruction(line: &str) -> Option<Instruction> {
+pub fn convert_to_instruction(line: &str) -> Result<Instruction, String> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"([NSEWLRF])([0-9]+)").unwrap();
+        static ref RE: Regex = Regex::new(r"^([NSEWLRF])([0-9]+)$").unwrap();
     }
-    let groups = RE.captures(line).expect("No match found on line");
-    let action = match groups.get(1).map(|a| a.as_str()) {
-        Some("N") => Action::North,
-        Some("S") => Action::South,
-        Some("E") => Action::East,
-        Some("W") => Action::West,
-        Some("L") => Action::Left,
-        Some("R") => Action::Right,
-        Some("F") => Action::Forward,
-        _ => return None,
-    };
-    let value = match groups.get(2).map(|m| m.as_str().parse()) {
-        Some(Ok(value)) => value,
-        _ => return None,
+    let groups = RE
+        .captures(line)
+        .ok_or_else(|| "expected an action of N, S, E, W, L, R or F and a number".to_string())?;
+    let action = match &groups[1] {
+        "N" => Action::North,
+        "S" => Action::South,
+        "E" => Action::East,
+        "W" => Action::West,
+        "L" => Action::Left,
+        "R" => Action::Right,
+        _ => Action::Forward,
     };
+    let value = groups[2]
+        .parse()
+        .map_err(|_| "the value is too large".to_string())?;
+    if (action == Action::Left || action == Action::Right) && value % 90 != 0 {
+        return Err(format!(
+            "can only turn in steps of 90 degrees, got {}",
+            value
+        ));
+    }
 
-    Some(Instruction { action, value })
+    Ok(Instruction { action, value })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_instructions() {
-        let input = "F10\nN3\nF7\nR90\nF11";
+        let input = EXAMPLES[0].input;
 
         let expected_instructions = vec![
             Instruction {
@@ -327,7 +364,20 @@ mod tests {
             },
         ];
 
-        assert_eq!(parse_instructions(input), expected_instructions);
+        assert_eq!(parse_instructions(input), Ok(expected_instructions));
+    }
+
+    #[test]
+    fn test_parse_instructions_invalid() {
+        let expected = Error::invalid_line(
+            DAY,
+            2,
+            "L45",
+            "can only turn in steps of 90 degrees, got 45",
+        );
+
+        assert_eq!(parse_instructions("F10\nL45\n"), Err(expected));
+        assert!(parse_instructions("F10\nU3\n").is_err());
     }
 
     #[test]
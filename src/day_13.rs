@@ -147,36 +147,56 @@
 ///
 /// What is the earliest timestamp such that all of the listed bus IDs depart at
 /// offsets matching their positions in the list?
+use crate::answer::Answer;
+use crate::error::{numbered_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use num::integer::lcm;
+use std::str::FromStr;
 
-const INPUT: &str = include_str!("../input/day_13.txt");
+const DAY: u8 = 13;
 
-pub fn run() {
-    let (earliest_timestamp, busses) = parse_bus_schedule(INPUT);
+/// The timestamp to start searching from
+pub const PARAMS: &[&str] = &["start"];
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        939\n\
+        7,13,x,x,59,x,31,19",
+    params: &[("start", "0")],
+    answers: &[Some("295"), Some("1068781")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let (earliest_timestamp, busses) = parse_bus_schedule(input)?;
 
     let (earliest_bus, wait_time) = find_earliest_bus(earliest_timestamp, &busses);
-    println!(
-        "The ID of the earliest bus multiplied by the wait time for that bus is: {}",
-        earliest_bus * wait_time
-    );
-
-    let contest_schedule = parse_bus_schedule_for_contest(INPUT);
-
-    let contest_timestamp = find_contest_timestamp(100000000000000, contest_schedule);
-    println!(
-        "The earliest timestamp that matches the contest conditions is: {}",
-        contest_timestamp
-    );
+    Ok(Answer::new(
+        "The ID of the earliest bus multiplied by the wait time for that bus is",
+        earliest_bus * wait_time,
+    ))
+}
+
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let contest_schedule = parse_bus_schedule_for_contest(input)?;
+    // the description promises the timestamp is at least this large for the real schedule
+    let start = params.get(DAY, "start", 100000000000000)?;
+
+    let contest_timestamp = find_contest_timestamp(start, contest_schedule);
+    Ok(Answer::new(
+        "The earliest timestamp that matches the contest conditions is",
+        contest_timestamp,
+    ))
 }
-fn find_earliest_bus(earliest_timestamp: u32, busses: &[u32]) -> (u32, u32) {
+pub fn find_earliest_bus(earliest_timestamp: u32, busses: &[u32]) -> (u32, u32) {
     busses
         .iter()
         .map(|bus_id| (*bus_id, bus_id - (earliest_timestamp % bus_id)))
-        .min_by(|(_, wait_time_a), (_, wait_time_b)| wait_time_a.cmp(&wait_time_b))
+        .min_by(|(_, wait_time_a), (_, wait_time_b)| wait_time_a.cmp(wait_time_b))
         .expect("Could not find a minimum wait time")
 }
 
-fn find_contest_timestamp(start_at: u64, mut schedule: Vec<(u64, u64)>) -> u64 {
+pub fn find_contest_timestamp(start_at: u64, mut schedule: Vec<(u64, u64)>) -> u64 {
     // precalculate the remainder the timestamp % bus operation should have
     schedule = schedule
         .into_iter()
@@ -199,59 +219,97 @@ fn find_contest_timestamp(start_at: u64, mut schedule: Vec<(u64, u64)>) -> u64 {
         // increase the cycle size to the lowest common multiple of the old cycle and the new bus
         cycle_size = lcm(cycle_size, bus);
     }
-    return timestamp_to_check;
+    timestamp_to_check
 }
 
-fn parse_bus_schedule(input: &str) -> (u32, Vec<u32>) {
-    let mut lines = input.lines();
-    let earliest_timestamp = lines
+pub fn parse_bus_schedule(input: &str) -> Result<(u32, Vec<u32>), Error> {
+    let mut lines = numbered_lines(input);
+    let (number, line) = lines
         .next()
-        .expect("No line with the earliest timestamp found")
+        .ok_or_else(|| Error::unexpected_end(DAY, "a line with the earliest timestamp"))?;
+    let earliest_timestamp = line
         .parse()
-        .expect("Earliest timestamp was not a number");
-    let busses = lines
+        .map_err(|_| Error::invalid_line(DAY, number, line, "expected the earliest timestamp"))?;
+    let (number, line) = lines
         .next()
-        .expect("No line with the busses found")
-        .split(',')
-        .map(str::parse)
-        .filter_map(Result::ok)
+        .ok_or_else(|| Error::unexpected_end(DAY, "a line with the busses"))?;
+    let busses = convert_to_busses(line)
+        .map_err(|reason| Error::invalid_line(DAY, number, line, reason))?
+        .into_iter()
+        .flatten()
         .collect();
-    (earliest_timestamp, busses)
+    Ok((earliest_timestamp, busses))
 }
 
-fn parse_bus_schedule_for_contest(input: &str) -> Vec<(u64, u64)> {
-    input
-        .lines()
-        .skip(1) // skip the earliest timestamp
-        .next()
-        .expect("No line with the busses found")
-        .split(',')
+pub fn parse_bus_schedule_for_contest(input: &str) -> Result<Vec<(u64, u64)>, Error> {
+    let (number, line) = numbered_lines(input)
+        .nth(1) // skip the earliest timestamp
+        .ok_or_else(|| Error::unexpected_end(DAY, "a line with the busses"))?;
+    let busses = convert_to_busses(line)
+        .map_err(|reason| Error::invalid_line(DAY, number, line, reason))?
+        .into_iter()
         .enumerate()
-        .map(|(offset, bus_id)| bus_id.parse().map(|bus| (bus, offset as u64)))
-        .filter_map(Result::ok)
+        .filter_map(|(offset, bus)| bus.map(|bus| (bus, offset as u64)))
+        .collect();
+    Ok(busses)
+}
+
+/// The bus IDs in the schedule, with `None` for the busses that are out of service.
+pub fn convert_to_busses<T: FromStr>(line: &str) -> Result<Vec<Option<T>>, String> {
+    line.split(',')
+        .map(|bus_id| match bus_id {
+            "x" => Ok(None),
+            _ => bus_id
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("expected a bus ID or x, got {}", bus_id)),
+        })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_bus_schedule() {
-        let input = "939\n7,13,x,x,59,x,31,19";
+        let input = EXAMPLES[0].input;
 
         let expected_schedule = (939, vec![7, 13, 59, 31, 19]);
 
-        assert_eq!(parse_bus_schedule(input), expected_schedule);
+        assert_eq!(parse_bus_schedule(input), Ok(expected_schedule));
     }
 
     #[test]
     fn test_parse_bus_schedule_for_contest() {
-        let input = "939\n7,13,x,x,59,x,31,19";
+        let input = EXAMPLES[0].input;
 
         let expected_schedule = vec![(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
 
-        assert_eq!(parse_bus_schedule_for_contest(input), expected_schedule);
+        assert_eq!(parse_bus_schedule_for_contest(input), Ok(expected_schedule));
+    }
+
+    #[test]
+    fn test_parse_bus_schedule_invalid() {
+        assert_eq!(
+            parse_bus_schedule("939\n"),
+            Err(Error::unexpected_end(DAY, "a line with the busses"))
+        );
+        assert_eq!(
+            parse_bus_schedule_for_contest("939\n7,13,y,59"),
+            Err(Error::invalid_line(
+                DAY,
+                2,
+                "7,13,y,59",
+                "expected a bus ID or x, got y"
+            ))
+        );
     }
 
     #[test]
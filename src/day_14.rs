@@ -138,30 +138,59 @@
 ///
 /// Execute the initialization program using an emulator for a version 2 decoder
 /// chip. What is the sum of all values left in memory after it completes?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../input/day_14.txt");
+const DAY: u8 = 14;
 
-pub fn run() {
-    let program = parse_program(INPUT);
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+            mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\n\
+            mem[8] = 11\n\
+            mem[7] = 101\n\
+            mem[8] = 0",
+        params: &[],
+        answers: &[Some("165"), None],
+    },
+    Example {
+        input: "\
+            mask = 000000000000000000000000000000X1001X\n\
+            mem[42] = 100\n\
+            mask = 00000000000000000000000000000000X0XX\n\
+            mem[26] = 1",
+        params: &[],
+        answers: &[None, Some("208")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let program = parse_program(input)?;
 
     let memory = run_program(&program);
     let sum_of_memory: u64 = memory.values().sum();
-    println!(
-        "The sum of all values left in memory after the initialization program completes is: {}",
-        sum_of_memory
-    );
+    Ok(Answer::new(
+        "The sum of all values left in memory after the initialization program completes is",
+        sum_of_memory,
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let program = parse_program(input)?;
 
     let memory_v2 = run_program_v2(&program);
     let sum_of_memory_v2: u64 = memory_v2.values().sum();
-    println!(
-        "The sum of all values left in memory after emulating v2 of the decoder chip is: {}",
-        sum_of_memory_v2
-    );
+    Ok(Answer::new(
+        "The sum of all values left in memory after emulating v2 of the decoder chip is",
+        sum_of_memory_v2,
+    ))
 }
 
-fn run_program(program: &[ProgramStep]) -> Memory {
+pub fn run_program(program: &[ProgramStep]) -> Memory {
     let mut mask = [None].iter().cycle().take(36).cloned().collect();
     let mut memory = Memory::new();
 
@@ -177,7 +206,7 @@ fn run_program(program: &[ProgramStep]) -> Memory {
     memory
 }
 
-fn run_program_v2(program: &[ProgramStep]) -> Memory {
+pub fn run_program_v2(program: &[ProgramStep]) -> Memory {
     let mut mask = [None].iter().cycle().take(36).cloned().collect();
     let mut memory = Memory::new();
 
@@ -195,11 +224,11 @@ fn run_program_v2(program: &[ProgramStep]) -> Memory {
     memory
 }
 
-fn apply_mask(value: u64, mask: &Mask) -> u64 {
+pub fn apply_mask(value: u64, mask: &Mask) -> u64 {
     let value_bit_vec = to_bit_vec(value);
     to_u64(
         mask.iter()
-            .zip(value_bit_vec.into_iter())
+            .zip(value_bit_vec)
             .map(|(&mask_bit, value_bit)| match mask_bit {
                 None => value_bit,
                 Some(n) => n,
@@ -208,12 +237,12 @@ fn apply_mask(value: u64, mask: &Mask) -> u64 {
     )
 }
 
-fn decode_address(address: u64, mask: &Mask) -> HashSet<u64> {
+pub fn decode_address(address: u64, mask: &Mask) -> HashSet<u64> {
     let address_bit_vec = to_bit_vec(address);
 
     let masked_address = mask
         .iter()
-        .zip(address_bit_vec.into_iter())
+        .zip(address_bit_vec)
         .map(|(&mask_bit, address_bit)| match mask_bit {
             None => None,
             Some(true) => Some(true),
@@ -224,7 +253,7 @@ fn decode_address(address: u64, mask: &Mask) -> HashSet<u64> {
     expand_masked_address(masked_address)
 }
 
-fn expand_masked_address(masked_address: Mask) -> HashSet<u64> {
+pub fn expand_masked_address(masked_address: Mask) -> HashSet<u64> {
     masked_address
         .into_iter()
         .fold(vec![0].into_iter().collect(), |addresses, bit| {
@@ -259,40 +288,37 @@ fn to_u64(bit_vec: Vec<bool>) -> u64 {
 }
 
 #[derive(Debug, PartialEq)]
-enum ProgramStep {
+pub enum ProgramStep {
     SetMask(Mask),
     SetMemory(u64, u64),
 }
 
-type Mask = Vec<Option<bool>>;
-type Memory = HashMap<u64, u64>;
+pub type Mask = Vec<Option<bool>>;
+pub type Memory = HashMap<u64, u64>;
 
-fn parse_program(input: &str) -> Vec<ProgramStep> {
-    input.lines().filter_map(convert_to_program_step).collect()
+pub fn parse_program(input: &str) -> Result<Vec<ProgramStep>, Error> {
+    parse_lines(DAY, input, convert_to_program_step)
 }
 
-fn convert_to_program_step(line: &str) -> Option<ProgramStep> {
+pub fn convert_to_program_step(line: &str) -> Result<ProgramStep, String> {
     lazy_static! {
         static ref MASK_RE: Regex = Regex::new(r"^mask = ([10X]{36})$").unwrap();
         static ref MEM_RE: Regex = Regex::new(r"^mem\[([0-9]+)\] = ([0-9]+)$").unwrap();
     }
-    if let Some(captures) = MASK_RE.captures(line) {
-        return create_mask_program_step(captures);
-    }
-    if let Some(captures) = MEM_RE.captures(line) {
-        return create_mem_program_step(captures);
-    }
-    None
+    let program_step = if let Some(captures) = MASK_RE.captures(line) {
+        create_mask_program_step(captures)
+    } else if let Some(captures) = MEM_RE.captures(line) {
+        create_mem_program_step(captures)
+    } else {
+        return Err("expected mask = <36 times 1, 0 or X> or mem[<address>] = <value>".to_string());
+    };
+    program_step.ok_or_else(|| "the address or value is too large".to_string())
 }
 
 fn create_mask_program_step(captures: regex::Captures) -> Option<ProgramStep> {
-    match captures
+    captures
         .get(1)
-        .map(|mask_match| create_mask(mask_match.as_str()))
-    {
-        Some(mask) => Some(ProgramStep::SetMask(mask)),
-        _ => None,
-    }
+        .map(|mask_match| ProgramStep::SetMask(create_mask(mask_match.as_str())))
 }
 
 fn create_mask(mask_str: &str) -> Mask {
@@ -324,14 +350,16 @@ fn create_mem_program_step(captures: regex::Captures) -> Option<ProgramStep> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_program() {
-        let input = "\
-            mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\n\
-            mem[8] = 11\n\
-            mem[7] = 101\n\
-            mem[8] = 0";
+        let input = EXAMPLES[0].input;
 
         let expected_program = vec![
             ProgramStep::SetMask(vec![
@@ -377,7 +405,7 @@ mod tests {
             ProgramStep::SetMemory(8, 0),
         ];
 
-        assert_eq!(parse_program(input), expected_program);
+        assert_eq!(parse_program(input), Ok(expected_program));
     }
 
     #[test]
@@ -73,28 +73,79 @@
 ///   - Given 3,1,2, the 30000000th number spoken is 362.
 ///
 /// Given your starting numbers, what will be the 30000000th number spoken?
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::example::Example;
+use crate::params::Params;
 use std::collections::HashMap;
 
-pub fn run() {
-    let start_sequence = vec![2, 0, 6, 12, 1, 3];
+const DAY: u8 = 15;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "0,3,6",
+        params: &[],
+        answers: &[Some("436"), Some("175594")],
+    },
+    Example {
+        input: "1,3,2",
+        params: &[],
+        answers: &[Some("1"), Some("2578")],
+    },
+    Example {
+        input: "2,1,3",
+        params: &[],
+        answers: &[Some("10"), Some("3544142")],
+    },
+    Example {
+        input: "1,2,3",
+        params: &[],
+        answers: &[Some("27"), Some("261214")],
+    },
+    Example {
+        input: "2,3,1",
+        params: &[],
+        answers: &[Some("78"), Some("6895259")],
+    },
+    Example {
+        input: "3,2,1",
+        params: &[],
+        answers: &[Some("438"), Some("18")],
+    },
+    Example {
+        input: "3,1,2",
+        params: &[],
+        answers: &[Some("1836"), Some("362")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let start_sequence = parse_start_sequence(input)?;
 
     let spoken_at_turn_2020 = play_memory_game(&start_sequence, 2020);
 
-    println!("The 2020th number spoken is: {}", spoken_at_turn_2020);
+    Ok(Answer::new(
+        "The 2020th number spoken is",
+        spoken_at_turn_2020,
+    ))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let start_sequence = parse_start_sequence(input)?;
 
     let spoken_at_turn_30000000 = play_memory_game(&start_sequence, 30000000);
 
-    println!(
-        "The 30000000th number spoken is: {}",
-        spoken_at_turn_30000000
-    );
+    Ok(Answer::new(
+        "The 30000000th number spoken is",
+        spoken_at_turn_30000000,
+    ))
 }
 
-fn play_memory_game(start_sequence: &[u32], goal: usize) -> u32 {
+pub fn play_memory_game(start_sequence: &[u32], goal: usize) -> u32 {
     // keep track of which turn a number was said on last
     let mut memory = HashMap::new();
 
-    let mut number = *start_sequence.get(0).expect("Start sequence is empty");
+    let mut number = *start_sequence.first().expect("Start sequence is empty");
 
     for turn in 1..goal {
         let new_number = match start_sequence.get(turn) {
@@ -111,9 +162,33 @@ fn play_memory_game(start_sequence: &[u32], goal: usize) -> u32 {
     number
 }
 
+pub fn parse_start_sequence(input: &str) -> Result<Vec<u32>, Error> {
+    let line = input.trim();
+    line.split(',')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| Error::invalid_line(DAY, 1, line, "expected numbers separated by commas"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        // part 2 plays 30 million turns, which is too slow without optimizations
+        assert_examples(EXAMPLES, &[part_1]);
+    }
+
+    #[test]
+    fn test_parse_start_sequence() {
+        let input = EXAMPLES[0].input;
+        let expected = vec![0, 3, 6];
+
+        assert_eq!(parse_start_sequence(input), Ok(expected));
+        assert!(parse_start_sequence("0,3,,6\n").is_err());
+    }
 
     #[test]
     fn test_play_memory_game_first_10_turns() {
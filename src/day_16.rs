@@ -103,16 +103,42 @@
 /// Once you work out which field is which, look for the six fields on your
 /// ticket that start with the word departure. What do you get if you multiply
 /// those six values together?
+use crate::answer::Answer;
+use crate::error::{convert_lines, numbered_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../input/day_16.txt");
-
-pub fn run() {
-    let notes = parse_notes(INPUT);
+const DAY: u8 = 16;
+
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        class: 1-3 or 5-7\n\
+        row: 6-11 or 33-44\n\
+        seat: 13-40 or 45-50\n\
+        \n\
+        your ticket:\n\
+        7,1,14\n\
+        \n\
+        nearby tickets:\n\
+        7,3,47\n\
+        40,4,50\n\
+        55,2,20\n\
+        38,6,12",
+    params: &[],
+    answers: &[Some("71"), None],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let notes = parse_notes(input)?;
 
     let error_rate = scanning_error_rate(&notes);
-    println!("The ticket scanning error rate is: {}", error_rate);
+    Ok(Answer::new("The ticket scanning error rate is", error_rate))
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let notes = parse_notes(input)?;
 
     let rule_mapping = map_rules_to_tickets(&notes);
     let departure_product: u64 = rule_mapping
@@ -125,13 +151,13 @@ pub fn run() {
             }
         })
         .product();
-    println!(
-        "The fields starting with the word departure in your ticket multipied is: {}",
-        departure_product
-    );
+    Ok(Answer::new(
+        "The fields starting with the word departure in your ticket multipied is",
+        departure_product,
+    ))
 }
 
-fn scanning_error_rate(notes: &Notes) -> u32 {
+pub fn scanning_error_rate(notes: &Notes) -> u32 {
     notes
         .nearby_tickets
         .iter()
@@ -140,7 +166,7 @@ fn scanning_error_rate(notes: &Notes) -> u32 {
         .sum()
 }
 
-fn map_rules_to_tickets<'a>(notes: &'a Notes) -> HashMap<&'a str, usize> {
+pub fn map_rules_to_tickets<'a>(notes: &'a Notes) -> HashMap<&'a str, usize> {
     let valid_tickets = filter_out_invalid_tickets(notes);
 
     // create a mapping to collect every possible column for every rule
@@ -151,7 +177,6 @@ fn map_rules_to_tickets<'a>(notes: &'a Notes) -> HashMap<&'a str, usize> {
             (
                 rule.name,
                 (0..notes.your_ticket.len())
-                    .into_iter()
                     .filter(|i| {
                         valid_tickets
                             .iter()
@@ -193,7 +218,7 @@ fn map_rules_to_tickets<'a>(notes: &'a Notes) -> HashMap<&'a str, usize> {
     final_mapping
 }
 
-fn filter_out_invalid_tickets<'a>(notes: &'a Notes) -> Vec<&'a Ticket> {
+pub fn filter_out_invalid_tickets<'a>(notes: &'a Notes) -> Vec<&'a Ticket> {
     notes
         .nearby_tickets
         .iter()
@@ -205,42 +230,51 @@ fn filter_out_invalid_tickets<'a>(notes: &'a Notes) -> Vec<&'a Ticket> {
         .collect()
 }
 
-fn parse_notes(input: &str) -> Notes {
-    let mut blocks = input.split("\n\n");
+pub fn parse_notes(input: &str) -> Result<Notes<'_>, Error> {
+    let mut lines = numbered_lines(input);
 
-    let rules = blocks
-        .next()
-        .expect("Expected a rules block")
-        .lines()
-        .map(convert_to_rule)
-        .collect();
+    // the rules end at the first empty line
+    let rules = convert_lines(
+        DAY,
+        lines.by_ref().take_while(|(_, line)| !line.is_empty()),
+        convert_to_rule,
+    )?;
 
-    let your_ticket = convert_to_ticket(
-        blocks
-            .next()
-            .expect("Expected a your ticket block")
-            .lines()
-            .skip(1)
-            .next()
-            .expect("Expected a line with your ticket"),
-    );
-
-    let nearby_tickets = blocks
-        .next()
-        .expect("Expected a nearby tickets block")
-        .lines()
-        .skip(1)
-        .map(convert_to_ticket)
-        .collect();
+    skip_header(&mut lines, "your ticket:")?;
+    let your_ticket = match lines.next() {
+        Some((number, line)) => convert_to_ticket(line)
+            .map_err(|reason| Error::invalid_line(DAY, number, line, reason))?,
+        None => return Err(Error::unexpected_end(DAY, "a line with your ticket")),
+    };
 
-    Notes {
+    skip_header(&mut lines, "nearby tickets:")?;
+    let nearby_tickets = convert_lines(DAY, lines, convert_to_ticket)?;
+
+    Ok(Notes {
         rules,
         your_ticket,
         nearby_tickets,
+    })
+}
+
+/// Skips the empty lines before the header of a section and the header itself
+fn skip_header<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    header: &str,
+) -> Result<(), Error> {
+    match lines.find(|(_, line)| !line.is_empty()) {
+        Some((_, line)) if line == header => Ok(()),
+        Some((number, line)) => Err(Error::invalid_line(
+            DAY,
+            number,
+            line,
+            format!("expected the header {}", header),
+        )),
+        None => Err(Error::unexpected_end(DAY, format!("the header {}", header))),
     }
 }
 
-fn convert_to_rule(line: &str) -> Rule {
+pub fn convert_to_rule(line: &str) -> Result<Rule<'_>, String> {
     lazy_static! {
         static ref NAME_RE: Regex = Regex::new(r"^([a-z ]+):").unwrap();
         static ref RANGE_RE: Regex = Regex::new(r"([0-9]+)-([0-9]+)").unwrap();
@@ -248,86 +282,83 @@ fn convert_to_rule(line: &str) -> Rule {
 
     let name = NAME_RE
         .captures(line)
-        .expect("Expected a match on the name")
+        .ok_or_else(|| "expected a rule like: <name>: 1-3 or 5-7".to_string())?
         .get(1)
-        .expect("Expected a group to be filled with the name")
+        .unwrap()
         .as_str();
 
-    let ranges = RANGE_RE
+    let ranges: Vec<_> = RANGE_RE
         .captures_iter(line)
-        .filter_map(|captures| {
-            match (
-                captures.get(1).unwrap().as_str().parse(),
-                captures.get(2).unwrap().as_str().parse(),
-            ) {
-                (Ok(min), Ok(max)) => Some(Range { min, max }),
-                _ => None,
-            }
-        })
-        .collect();
+        .map(
+            |captures| match (captures[1].parse(), captures[2].parse()) {
+                (Ok(min), Ok(max)) => Ok(Range { min, max }),
+                _ => Err(format!("the range {} is too large", &captures[0])),
+            },
+        )
+        .collect::<Result<_, _>>()?;
+    if ranges.is_empty() {
+        return Err(format!("expected the ranges of {}", name));
+    }
 
-    Rule { name, ranges }
+    Ok(Rule { name, ranges })
 }
 
-fn convert_to_ticket(line: &str) -> Ticket {
-    line.split(",")
-        .map(str::parse)
-        .filter_map(Result::ok)
+pub fn convert_to_ticket(line: &str) -> Result<Ticket, String> {
+    line.split(',')
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("expected the values of a ticket, got {}", value))
+        })
         .collect()
 }
 
 #[derive(Debug, PartialEq)]
-struct Notes<'a> {
-    rules: Vec<Rule<'a>>,
-    your_ticket: Ticket,
-    nearby_tickets: Vec<Ticket>,
+pub struct Notes<'a> {
+    pub rules: Vec<Rule<'a>>,
+    pub your_ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
 }
 
 #[derive(Debug, PartialEq)]
-struct Rule<'a> {
-    name: &'a str,
-    ranges: Vec<Range>,
+pub struct Rule<'a> {
+    pub name: &'a str,
+    pub ranges: Vec<Range>,
 }
 
 impl Rule<'_> {
-    fn valid(&self, value: &u32) -> bool {
+    pub fn valid(&self, value: &u32) -> bool {
         self.ranges.iter().any(|range| range.contains(value))
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Range {
-    min: u32,
-    max: u32,
+pub struct Range {
+    pub min: u32,
+    pub max: u32,
 }
 
 impl Range {
-    fn contains(&self, value: &u32) -> bool {
+    pub fn contains(&self, value: &u32) -> bool {
         self.min <= *value && *value <= self.max
     }
 }
 
-type Ticket = Vec<u32>;
+pub type Ticket = Vec<u32>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_ticket_notes() {
-        let input = "\
-            class: 1-3 or 5-7\n\
-            row: 6-11 or 33-44\n\
-            seat: 13-40 or 45-50\n\
-            \n\
-            your ticket:\n\
-            7,1,14\n\
-            \n\
-            nearby tickets:\n\
-            7,3,47\n\
-            40,4,50\n\
-            55,2,20\n\
-            38,6,12";
+        let input = EXAMPLES[0].input;
 
         let rules = vec![
             Rule {
@@ -357,7 +388,32 @@ mod tests {
             nearby_tickets,
         };
 
-        assert_eq!(parse_notes(input), expected_notes);
+        assert_eq!(parse_notes(input), Ok(expected_notes));
+    }
+
+    #[test]
+    fn test_parse_ticket_notes_invalid() {
+        let input = "\
+            class: 1-3 or 5-7\n\
+            \n\
+            your ticket:\n\
+            7,1,14\n\
+            \n\
+            nearby tickets:\n\
+            7,3,47\n\
+            40,four,50";
+        let expected = Error::invalid_line(
+            DAY,
+            8,
+            "40,four,50",
+            "expected the values of a ticket, got four",
+        );
+
+        assert_eq!(parse_notes(input), Err(expected));
+        assert_eq!(
+            parse_notes("class: 1-3 or 5-7\n\nyour ticket:\n"),
+            Err(Error::unexpected_end(DAY, "a line with your ticket"))
+        );
     }
 
     #[test]
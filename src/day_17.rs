@@ -436,33 +436,49 @@
 /// Starting with your given initial configuration, simulate six cycles in a
 /// 4-dimensional space. How many cubes are left in the active state after the
 /// sixth cycle?
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::example::Example;
+use crate::grid::Grid;
+use crate::params::Params;
 use num::iter::range_inclusive;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
-const INPUT: &str = include_str!("../input/day_17.txt");
+const DAY: u8 = 17;
 
-pub fn run() {
-    let mut cubes_3d = parse_cubes::<Point3D>(INPUT);
+pub const EXAMPLES: &[Example] = &[Example {
+    input: "\
+        .#.\n\
+        ..#\n\
+        ###",
+    params: &[],
+    answers: &[Some("112"), Some("848")],
+}];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let mut cubes_3d = parse_cubes::<Point3D>(input)?;
     for _ in 0..6 {
         cubes_3d = iterate(cubes_3d);
     }
-    println!(
-        "After simulating six cycles the amount of cubes left in the activated state is: {}",
-        cubes_3d.len()
-    );
+    Ok(Answer::new(
+        "After simulating six cycles the amount of cubes left in the activated state is",
+        cubes_3d.len(),
+    ))
+}
 
-    let mut cubes_4d = parse_cubes::<Point4D>(INPUT);
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let mut cubes_4d = parse_cubes::<Point4D>(input)?;
     for _ in 0..6 {
         cubes_4d = iterate(cubes_4d);
     }
-    println!(
-        "After six cycles in 4-dimensional space the amount of activated cubes is: {}",
-        cubes_4d.len()
-    );
+    Ok(Answer::new(
+        "After six cycles in 4-dimensional space the amount of activated cubes is",
+        cubes_4d.len(),
+    ))
 }
 
-fn get_neighbour_counts<D: Point>(cubes: &Cubes<D>) -> HashMap<D, usize> {
+pub fn get_neighbour_counts<D: Point>(cubes: &Cubes<D>) -> HashMap<D, usize> {
     cubes
         .iter()
         .flat_map(|point| point.neighbours())
@@ -472,7 +488,7 @@ fn get_neighbour_counts<D: Point>(cubes: &Cubes<D>) -> HashMap<D, usize> {
         })
 }
 
-fn iterate<D: Point>(cubes: Cubes<D>) -> Cubes<D> {
+pub fn iterate<D: Point>(cubes: Cubes<D>) -> Cubes<D> {
     let neighbour_counts = get_neighbour_counts(&cubes);
 
     neighbour_counts
@@ -489,23 +505,22 @@ fn iterate<D: Point>(cubes: Cubes<D>) -> Cubes<D> {
 }
 
 // marker trait for Points
-trait Point: Sized + Eq + Clone + Hash {
+pub trait Point: Sized + Eq + Clone + Hash {
     fn neighbours(&self) -> Vec<Self>;
 
     fn new(x: i32, y: i32) -> Self;
 }
 
-type Point3D = (i32, i32, i32);
+pub type Point3D = (i32, i32, i32);
 impl Point for Point3D {
     fn neighbours(&self) -> Vec<Self> {
         let (x, y, z) = *self;
         range_inclusive(-1, 1)
-            .map(move |dz| {
-                range_inclusive(-1, 1)
-                    .map(move |dy| range_inclusive(-1, 1).map(move |dx| (x + dx, y + dy, z + dz)))
-                    .flatten()
+            .flat_map(move |dz| {
+                range_inclusive(-1, 1).flat_map(move |dy| {
+                    range_inclusive(-1, 1).map(move |dx| (x + dx, y + dy, z + dz))
+                })
             })
-            .flatten()
             .filter(|point| *point != (x, y, z))
             .collect()
     }
@@ -515,24 +530,18 @@ impl Point for Point3D {
     }
 }
 
-type Point4D = (i32, i32, i32, i32);
+pub type Point4D = (i32, i32, i32, i32);
 impl Point for Point4D {
     fn neighbours(&self) -> Vec<Self> {
         let (x, y, z, w) = *self;
         range_inclusive(-1, 1)
-            .map(move |dw| {
-                range_inclusive(-1, 1)
-                    .map(move |dz| {
-                        range_inclusive(-1, 1)
-                            .map(move |dy| {
-                                range_inclusive(-1, 1)
-                                    .map(move |dx| (x + dx, y + dy, z + dz, w + dw))
-                            })
-                            .flatten()
+            .flat_map(move |dw| {
+                range_inclusive(-1, 1).flat_map(move |dz| {
+                    range_inclusive(-1, 1).flat_map(move |dy| {
+                        range_inclusive(-1, 1).map(move |dx| (x + dx, y + dy, z + dz, w + dw))
                     })
-                    .flatten()
+                })
             })
-            .flatten()
             .filter(|point| *point != (x, y, z, w))
             .collect()
     }
@@ -541,29 +550,34 @@ impl Point for Point4D {
         (x, y, 0, 0)
     }
 }
-type Cubes<D> = HashSet<D>;
+pub type Cubes<D> = HashSet<D>;
 
-fn parse_cubes<D: Point>(input: &str) -> Cubes<D> {
-    input
-        .lines()
-        .enumerate()
-        .map(|(x, line)| {
-            line.chars().enumerate().filter_map(move |(y, c)| match c {
-                '#' => Some(D::new(x as i32, y as i32)),
-                _ => None,
-            })
-        })
-        .flatten()
-        .collect()
+pub fn parse_cubes<D: Point>(input: &str) -> Result<Cubes<D>, Error> {
+    let grid = Grid::parse(DAY, input, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(format!("unknown cube state {}, expected # or .", c)),
+    })?;
+    Ok(grid
+        .iter()
+        .filter(|(_, &active)| active)
+        .map(|((row, column), _)| D::new(row as i32, column as i32))
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_parse_cubes() {
-        let input = ".#.\n..#\n###";
+        let input = EXAMPLES[0].input;
 
         let mut expected_cubes = Cubes::new();
         // .#.
@@ -575,7 +589,7 @@ mod tests {
         expected_cubes.insert((2, 1, 0));
         expected_cubes.insert((2, 2, 0));
 
-        assert_eq!(parse_cubes(input), expected_cubes);
+        assert_eq!(parse_cubes(input), Ok(expected_cubes));
     }
 
     #[test]
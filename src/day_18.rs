@@ -79,30 +79,71 @@
 ///
 /// What do you get if you add up the results of evaluating the homework
 /// problems using these new rules?
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use std::ops::{Add, Mul};
 
-const INPUT: &str = include_str!("../input/day_18.txt");
-
-pub fn run() {
-    let homework = INPUT;
+const DAY: u8 = 18;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "1 + 2 * 3 + 4 * 5 + 6",
+        params: &[],
+        answers: &[Some("71"), Some("231")],
+    },
+    Example {
+        input: "1 + (2 * 3) + (4 * (5 + 6))",
+        params: &[],
+        answers: &[Some("51"), Some("51")],
+    },
+    Example {
+        input: "2 * 3 + (4 * 5)",
+        params: &[],
+        answers: &[Some("26"), Some("46")],
+    },
+    Example {
+        input: "5 + (8 * 3 + 9 + 3 * 4 * 3)",
+        params: &[],
+        answers: &[Some("437"), Some("1445")],
+    },
+    Example {
+        input: "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))",
+        params: &[],
+        answers: &[Some("12240"), Some("669060")],
+    },
+    Example {
+        input: "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+        params: &[],
+        answers: &[Some("13632"), Some("23340")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let homework = parse_homework(input)?;
+
+    Ok(Answer::new(
+        "The sum of all lines of homework is",
+        homework.into_iter().map(evaluate).sum::<u64>(),
+    ))
+}
 
-    println!(
-        "The sum of all lines of homework is: {}",
-        homework.lines().map(evaluate).sum::<u64>()
-    );
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let homework = parse_homework(input)?;
 
-    println!(
-        "Adding up the results of all the homework problems using the new rules gives: {}",
-        homework.lines().map(advanced_evaluate).sum::<u64>()
-    );
+    Ok(Answer::new(
+        "Adding up the results of all the homework problems using the new rules gives",
+        homework.into_iter().map(advanced_evaluate).sum::<u64>(),
+    ))
 }
 
-fn evaluate(expression: &str) -> u64 {
+pub fn evaluate(expression: &str) -> u64 {
     let mut characters = expression.chars().filter(|&c| c != ' ');
     sub_evaluate(&mut characters)
 }
 
-fn sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
+pub fn sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
     let mut total = None;
     let mut operator: Option<fn(u64, u64) -> u64> = None;
     while let Some(character) = characters.next() {
@@ -133,12 +174,12 @@ fn sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
     total.expect("Expected a non-empty expression")
 }
 
-fn advanced_evaluate(expression: &str) -> u64 {
+pub fn advanced_evaluate(expression: &str) -> u64 {
     let mut characters = expression.chars().filter(|&c| c != ' ');
     advanced_sub_evaluate(&mut characters)
 }
 
-fn advanced_sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
+pub fn advanced_sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
     let mut number_stack = Vec::new();
     let mut is_addition = false;
 
@@ -173,9 +214,39 @@ fn advanced_sub_evaluate(characters: &mut impl Iterator<Item = char>) -> u64 {
     number_stack.iter().product()
 }
 
+pub fn parse_homework(input: &str) -> Result<Vec<&str>, Error> {
+    parse_lines(DAY, input, convert_to_expression)
+}
+
+/// Checks that the line is an expression the evaluators can handle: single digit numbers and
+/// expressions between parentheses, separated by + or *.
+pub fn convert_to_expression(line: &str) -> Result<&str, String> {
+    let mut depth = 0;
+    let mut expect_operand = true;
+    for character in line.chars().filter(|&c| c != ' ') {
+        match (character, expect_operand) {
+            ('(', true) => depth += 1,
+            (')', false) if depth > 0 => depth -= 1,
+            ('+' | '*', false) => expect_operand = true,
+            (digit, true) if digit.is_ascii_digit() => expect_operand = false,
+            _ => return Err(format!("unexpected {} in the expression", character)),
+        }
+    }
+    if expect_operand || depth > 0 {
+        return Err("the expression is incomplete".to_string());
+    }
+    Ok(line)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
 
     #[test]
     fn test_evaluate_1() {
@@ -260,4 +331,18 @@ mod tests {
 
         assert_eq!(advanced_evaluate(expression), 23340);
     }
+
+    #[test]
+    fn test_parse_homework() {
+        let input = "1 + (2 * 3)\n2 * 3 + (4 * 5)\n";
+
+        assert_eq!(
+            parse_homework(input),
+            Ok(vec!["1 + (2 * 3)", "2 * 3 + (4 * 5)"])
+        );
+        assert!(parse_homework("1 + (2 * 3").is_err());
+        assert!(parse_homework("1 + * 3").is_err());
+        assert!(parse_homework("12 + 3").is_err());
+        assert!(parse_homework("1 + 2)").is_err());
+    }
 }
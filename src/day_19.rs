@@ -173,31 +173,110 @@
 ///     aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
 ///
 /// After updating rules 8 and 11, how many messages completely match rule 0?
+use crate::answer::Answer;
+use crate::error::{numbered_lines, parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::prelude::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../input/day_19.txt");
+const DAY: u8 = 19;
 
-pub fn run() {
-    let mut input_blocks = INPUT.split("\n\n");
-
-    let rules = parse_rules(input_blocks.next().expect("Expected a rules block"));
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+            0: 4 1 5\n\
+            1: 2 3 | 3 2\n\
+            2: 4 4 | 5 5\n\
+            3: 4 5 | 5 4\n\
+            4: \"a\"\n\
+            5: \"b\"\n\
+            \n\
+            ababbb\n\
+            bababa\n\
+            abbbab\n\
+            aaabbb\n\
+            aaaabbb",
+        params: &[],
+        answers: &[Some("2")],
+    },
+    Example {
+        input: "\
+            42: 9 14 | 10 1\n\
+            9: 14 27 | 1 26\n\
+            10: 23 14 | 28 1\n\
+            1: \"a\"\n\
+            11: 42 31\n\
+            5: 1 14 | 15 1\n\
+            19: 14 1 | 14 14\n\
+            12: 24 14 | 19 1\n\
+            16: 15 1 | 14 14\n\
+            31: 14 17 | 1 13\n\
+            6: 14 14 | 1 14\n\
+            2: 1 24 | 14 4\n\
+            0: 8 11\n\
+            13: 14 3 | 1 12\n\
+            15: 1 | 14\n\
+            17: 14 2 | 1 7\n\
+            23: 25 1 | 22 14\n\
+            28: 16 1\n\
+            4: 1 1\n\
+            20: 14 14 | 1 15\n\
+            3: 5 14 | 16 1\n\
+            27: 1 6 | 14 18\n\
+            14: \"b\"\n\
+            21: 14 1 | 1 14\n\
+            25: 1 1 | 1 14\n\
+            22: 14 14\n\
+            8: 42\n\
+            26: 14 22 | 1 20\n\
+            18: 15 15\n\
+            7: 14 5 | 1 21\n\
+            24: 14 1\n\
+            \n\
+            abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa\n\
+            bbabbbbaabaabba\n\
+            babbbbaabbbbbabbbbbbaabaaabaaa\n\
+            aaabbbbbbaaaabaababaabababbabaaabbababababaaa\n\
+            bbbbbbbaaaabbbbaaabbabaaa\n\
+            bbbababbbbaaaaaaaabbababaaababaabab\n\
+            ababaaaaaabaaab\n\
+            ababaaaaabbbaba\n\
+            baabbaaaabbaaaababbaababb\n\
+            abbbbabbbbaaaababbbbbbaaaababb\n\
+            aaaaabbaabaaaaababaa\n\
+            aaaabbaaaabbaaa\n\
+            aaaabbaabbaaaaaaabbbabbbaaabbaabaaa\n\
+            babaaabbbaaabaababbaabababaaab\n\
+            aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba",
+        params: &[],
+        answers: &[Some("3")],
+    },
+];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let mut input_blocks = input.split("\n\n");
+
+    // the first block always exists, even when the input is empty
+    let rules = parse_rules(input_blocks.next().unwrap())?;
     let (possibility_tree, root) = build_possibility_tree(&rules);
 
-    let messages = input_blocks.next().expect("Expected a messages block");
+    let messages = input_blocks
+        .next()
+        .ok_or_else(|| Error::unexpected_end(DAY, "a block of messages after the rules"))?;
 
     let matching_rule_0 = messages
         .lines()
         .filter(|message| validate_message(message, &possibility_tree, root))
         .count();
-    println!(
-        "The amount of messages that completely match rule 0 is: {}",
-        matching_rule_0
-    );
+    Ok(Answer::new(
+        "The amount of messages that completely match rule 0 is",
+        matching_rule_0,
+    ))
 }
 
-fn validate_message(message: &str, tree: &PossibilityTree, root: NodeIndex) -> bool {
+pub fn validate_message(message: &str, tree: &PossibilityTree, root: NodeIndex) -> bool {
     // start with a head at the root of the tree
     let mut heads = vec![root];
 
@@ -205,8 +284,7 @@ fn validate_message(message: &str, tree: &PossibilityTree, root: NodeIndex) -> b
     for letter in message.chars() {
         heads = heads
             .into_iter()
-            .map(|node| tree.edges(node))
-            .flatten()
+            .flat_map(|node| tree.edges(node))
             .filter_map(|edge| match edge.weight() {
                 Branch::Normal(c) if c == &letter => Some(edge.target()),
                 _ => None,
@@ -220,13 +298,13 @@ fn validate_message(message: &str, tree: &PossibilityTree, root: NodeIndex) -> b
         .any(|node| tree.edges(node).next().is_none())
 }
 
-type PossibilityTree = DiGraph<u32, Branch>;
+pub type PossibilityTree = DiGraph<u32, Branch>;
 #[derive(Debug, PartialEq)]
-enum Branch {
+pub enum Branch {
     Normal(char),
 }
 
-fn build_possibility_tree(rules: &Rules) -> (PossibilityTree, NodeIndex) {
+pub fn build_possibility_tree(rules: &Rules) -> (PossibilityTree, NodeIndex) {
     let mut tree = PossibilityTree::new();
 
     let begin = tree.add_node(0);
@@ -272,51 +350,73 @@ fn expand_chain(
     }
 }
 
-type Rules = HashMap<RuleName, Vec<RuleOption>>;
-type RuleName = u16;
+pub type Rules = HashMap<RuleName, Vec<RuleOption>>;
+pub type RuleName = u16;
 #[derive(Debug, PartialEq)]
-enum RuleOption {
+pub enum RuleOption {
     Chain(Vec<RuleName>),
     Literal(char),
 }
 
-fn parse_rules(input: &str) -> Rules {
-    input
-        .lines()
-        .map(|line| {
-            let mut line_iter = line.split(": ");
-            let rule_name = line_iter
-                .next()
-                .and_then(|name| name.parse().ok())
-                .expect("Expected a integer rule name");
-            let rule_options = line_iter
-                .next()
-                .map(convert_to_rule_options)
-                .expect("Expected rule options");
-            (rule_name, rule_options)
-        })
-        .collect()
+pub fn parse_rules(input: &str) -> Result<Rules, Error> {
+    let rules = parse_lines(DAY, input, convert_to_rule)?;
+
+    // every rule that is referred to has to exist to be able to build the possibility tree
+    let rule_names: HashSet<_> = rules.iter().map(|(rule_name, _)| *rule_name).collect();
+    if !rule_names.contains(&0) {
+        return Err(Error::unexpected_end(
+            DAY,
+            "a rule 0 to match the messages with",
+        ));
+    }
+    for ((number, line), (_, rule_options)) in numbered_lines(input).zip(rules.iter()) {
+        for rule_option in rule_options {
+            if let RuleOption::Chain(chain) = rule_option {
+                if let Some(missing) = chain.iter().find(|name| !rule_names.contains(name)) {
+                    let reason = format!("rule {} does not exist", missing);
+                    return Err(Error::invalid_line(DAY, number, line, reason));
+                }
+            }
+        }
+    }
+    Ok(rules.into_iter().collect())
 }
 
-fn convert_to_rule_options(options: &str) -> Vec<RuleOption> {
+pub fn convert_to_rule(line: &str) -> Result<(RuleName, Vec<RuleOption>), String> {
+    let (rule_name, rule_options) = line
+        .split_once(": ")
+        .ok_or_else(|| "expected a rule like: <number>: <options>".to_string())?;
+    let rule_name = rule_name
+        .parse()
+        .map_err(|_| format!("expected a number as the rule name, got {}", rule_name))?;
+    Ok((rule_name, convert_to_rule_options(rule_options)?))
+}
+
+pub fn convert_to_rule_options(options: &str) -> Result<Vec<RuleOption>, String> {
     // check if the options are a literal
-    if options.starts_with("\"") {
-        return vec![RuleOption::Literal(
-            options.trim_matches('"').chars().next().unwrap(),
-        )];
+    if options.starts_with('"') {
+        return match options.trim_matches('"').chars().collect::<Vec<_>>()[..] {
+            [literal] => Ok(vec![RuleOption::Literal(literal)]),
+            _ => Err(format!(
+                "expected a single character literal, got {}",
+                options
+            )),
+        };
     }
 
     // or options of chains
     options
-        .split("|")
+        .split('|')
         .map(|option| {
-            RuleOption::Chain(
-                option
-                    .split_whitespace()
-                    .map(str::parse)
-                    .filter_map(Result::ok)
-                    .collect(),
-            )
+            option
+                .split_whitespace()
+                .map(|rule_name| {
+                    rule_name
+                        .parse()
+                        .map_err(|_| format!("expected a rule number, got {}", rule_name))
+                })
+                .collect::<Result<_, _>>()
+                .map(RuleOption::Chain)
         })
         .collect()
 }
@@ -324,6 +424,12 @@ fn convert_to_rule_options(options: &str) -> Vec<RuleOption> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1]);
+    }
     use petgraph::algo::is_isomorphic_matching;
 
     #[test]
@@ -353,7 +459,29 @@ mod tests {
         expected_rules.insert(4, vec![RuleOption::Literal('a')]);
         expected_rules.insert(5, vec![RuleOption::Literal('b')]);
 
-        assert_eq!(parse_rules(input), expected_rules);
+        assert_eq!(parse_rules(input), Ok(expected_rules));
+    }
+
+    #[test]
+    fn test_parse_rules_invalid() {
+        assert_eq!(
+            parse_rules("0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: b"),
+            Err(Error::invalid_line(
+                DAY,
+                4,
+                "3: b",
+                "expected a rule number, got b"
+            ))
+        );
+        assert_eq!(
+            parse_rules("0: 1 2\n1: \"a\"\n2: 1 3 | 3 1"),
+            Err(Error::invalid_line(
+                DAY,
+                3,
+                "2: 1 3 | 3 1",
+                "rule 3 does not exist"
+            ))
+        );
     }
 
     #[test]
@@ -0,0 +1,267 @@
+use std::fmt;
+
+/// Everything that can be wrong with the puzzle input of a day.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// A line, or a block of lines, that could not be parsed
+    InvalidLine {
+        day: u8,
+        /// The line number, starting at 1
+        line: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input ended while more was expected
+    UnexpectedEnd { day: u8, expected: String },
+    /// A parameter with a value the day can't use
+    InvalidParameter {
+        day: u8,
+        name: String,
+        value: String,
+        /// Why the value can't be used, when there is more to say than that it is invalid
+        reason: Option<String>,
+    },
+    /// The input is valid, but has no answer to the question
+    NoAnswer { day: u8, reason: String },
+}
+
+impl Error {
+    pub fn invalid_line(day: u8, line: usize, text: &str, reason: impl Into<String>) -> Error {
+        Error::InvalidLine {
+            day,
+            line,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn unexpected_end(day: u8, expected: impl Into<String>) -> Error {
+        Error::UnexpectedEnd {
+            day,
+            expected: expected.into(),
+        }
+    }
+
+    pub fn invalid_parameter(day: u8, name: &str, value: &str) -> Error {
+        Error::InvalidParameter {
+            day,
+            name: name.to_string(),
+            value: value.to_string(),
+            reason: None,
+        }
+    }
+
+    pub fn invalid_parameter_because(
+        day: u8,
+        name: &str,
+        value: &str,
+        reason: impl Into<String>,
+    ) -> Error {
+        Error::InvalidParameter {
+            day,
+            name: name.to_string(),
+            value: value.to_string(),
+            reason: Some(reason.into()),
+        }
+    }
+
+    pub fn no_answer(day: u8, reason: impl Into<String>) -> Error {
+        Error::NoAnswer {
+            day,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidLine {
+                day,
+                line,
+                text,
+                reason,
+            } => {
+                write!(f, "Day {}, line {}: {}", day, line, reason)?;
+                for text_line in text.lines() {
+                    write!(f, "\n    {}", text_line)?;
+                }
+                Ok(())
+            }
+            Error::UnexpectedEnd { day, expected } => {
+                write!(f, "Day {}: the input ended, expected {}", day, expected)
+            }
+            Error::InvalidParameter {
+                day,
+                name,
+                value,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Day {}: invalid value {} for the parameter {}",
+                    day, value, name
+                )?;
+                match reason {
+                    Some(reason) => write!(f, ". {}", reason),
+                    None => Ok(()),
+                }
+            }
+            Error::NoAnswer { day, reason } => write!(f, "Day {}: no answer, {}", day, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The lines of an input together with their line number, starting at 1
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// Converts numbered lines, or blocks of lines, one by one. The first that can't be converted
+/// becomes the error.
+pub fn convert_lines<'a, T>(
+    day: u8,
+    lines: impl Iterator<Item = (usize, &'a str)>,
+    convert: impl Fn(&'a str) -> Result<T, String>,
+) -> Result<Vec<T>, Error> {
+    lines
+        .map(|(number, line)| {
+            convert(line).map_err(|reason| Error::invalid_line(day, number, line, reason))
+        })
+        .collect()
+}
+
+/// Converts every line of the input.
+pub fn parse_lines<'a, T>(
+    day: u8,
+    input: &'a str,
+    convert: impl Fn(&'a str) -> Result<T, String>,
+) -> Result<Vec<T>, Error> {
+    convert_lines(day, numbered_lines(input), convert)
+}
+
+/// Converts every line of the input that can be converted, together with its line number. The
+/// lines that can't be converted are skipped and returned as errors, so the caller can decide
+/// what to do with them.
+pub fn parse_lines_lenient<'a, T>(
+    day: u8,
+    input: &'a str,
+    convert: impl Fn(&'a str) -> Result<T, String>,
+) -> (Vec<(usize, T)>, Vec<Error>) {
+    let mut converted = vec![];
+    let mut skipped = vec![];
+    for (number, line) in numbered_lines(input) {
+        match convert(line) {
+            Ok(value) => converted.push((number, value)),
+            Err(reason) => skipped.push(Error::invalid_line(day, number, line, reason)),
+        }
+    }
+    (converted, skipped)
+}
+
+/// The blocks of lines of an input together with the number of their first line, starting at 1.
+/// Blocks are separated by an empty line.
+pub fn numbered_blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut line = 1;
+    input.split("\n\n").map(move |block| {
+        let first_line = line;
+        // the lines of the block and the empty line after it
+        line += block.split('\n').count() + 1;
+        (first_line, block)
+    })
+}
+
+/// Converts every block of lines of the input, blocks are separated by an empty line. Errors
+/// point at the first line of the block.
+pub fn parse_blocks<'a, T>(
+    day: u8,
+    input: &'a str,
+    convert: impl Fn(&'a str) -> Result<T, String>,
+) -> Result<Vec<T>, Error> {
+    convert_lines(day, numbered_blocks(input), convert)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_number(line: &str) -> Result<u32, String> {
+        line.parse()
+            .map_err(|_| format!("expected a number, got {}", line))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines(1, "1\n2\n3\n", parse_number), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse_lines(1, "1\n2\nthree\n", parse_number),
+            Err(Error::invalid_line(
+                1,
+                3,
+                "three",
+                "expected a number, got three"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_lenient() {
+        assert_eq!(
+            parse_lines_lenient(1, "1\ntwo\n3\n", parse_number),
+            (
+                vec![(1, 1), (3, 3)],
+                vec![Error::invalid_line(
+                    1,
+                    2,
+                    "two",
+                    "expected a number, got two"
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let input = "a\nb\n\nc\n\nd\ne\n";
+        let convert = |block: &str| match block.contains('c') {
+            true => Err("c is not allowed".to_string()),
+            false => Ok(block.lines().count()),
+        };
+
+        assert_eq!(
+            parse_blocks(6, input, convert),
+            Err(Error::invalid_line(6, 4, "c", "c is not allowed"))
+        );
+        assert_eq!(parse_blocks(6, "a\nb\n\nd\ne\n", convert), Ok(vec![2, 2]));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::invalid_line(4, 12, "ecl:gry\nxyz:1", "unknown field code xyz").to_string(),
+            "Day 4, line 12: unknown field code xyz\n    ecl:gry\n    xyz:1"
+        );
+        assert_eq!(
+            Error::unexpected_end(13, "a line with the busses").to_string(),
+            "Day 13: the input ended, expected a line with the busses"
+        );
+        assert_eq!(
+            Error::invalid_parameter(9, "preamble", "five").to_string(),
+            "Day 9: invalid value five for the parameter preamble"
+        );
+        assert_eq!(
+            Error::invalid_parameter_because(2, "policy", "length", "Unknown policy length")
+                .to_string(),
+            "Day 2: invalid value length for the parameter policy. Unknown policy length"
+        );
+        assert_eq!(
+            Error::no_answer(1, "no 2 entries sum to 2020").to_string(),
+            "Day 1: no answer, no 2 entries sum to 2020"
+        );
+    }
+}
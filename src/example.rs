@@ -0,0 +1,67 @@
+use crate::modules::Part;
+use crate::params::Params;
+
+/// An example from the puzzle description, with the answers the description gives for it.
+///
+/// Every day lists its examples once in `EXAMPLES`, they are solved by `--example` on the
+/// command line and by the unit tests of the day.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    /// Parameters that differ from the real puzzle, like a shorter preamble
+    pub params: &'static [(&'static str, &'static str)],
+    /// The answer to part 1 first, then part 2. `None` for parts the example doesn't cover.
+    pub answers: &'static [Option<&'static str>],
+}
+
+impl Example {
+    pub fn params(&self) -> Params {
+        Params::from(self.params)
+    }
+
+    /// The expected answer to a part, parts start at 1
+    pub fn answer(&self, part: usize) -> Option<&'static str> {
+        part.checked_sub(1)
+            .and_then(|index| self.answers.get(index).copied())
+            .flatten()
+    }
+}
+
+/// Solves the examples with the given parts, panicking when a part fails or gives a different
+/// answer. The parts that are left out are not run, for example because they are too slow
+/// without optimizations.
+pub fn assert_examples(examples: &[Example], parts: &[Part]) {
+    for (index, example) in examples.iter().enumerate() {
+        let params = example.params();
+        for (part, solve) in (1..).zip(parts) {
+            let expected = match example.answer(part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let context = format!("Example {} part {}", index + 1, part);
+            match solve(example.input, &params) {
+                Ok(answer) => assert_eq!(answer.value.to_string(), expected, "{}", context),
+                Err(error) => panic!("{}: {}", context, error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: Example = Example {
+        input: "1",
+        params: &[("times", "3")],
+        answers: &[Some("3"), None],
+    };
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(EXAMPLE.answer(1), Some("3"));
+        assert_eq!(EXAMPLE.answer(2), None);
+        assert_eq!(EXAMPLE.answer(3), None);
+        assert_eq!(EXAMPLE.answer(0), None);
+    }
+}
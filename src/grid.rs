@@ -0,0 +1,282 @@
+//! A rectangle of cells, like the maps and seating areas of several days.
+//!
+//! The cells are stored row by row in a single `Vec`, a position is a row and a column counting
+//! from the top left. Moving over the grid can wrap around its edges, like the map of day 3
+//! that repeats to the right.
+use crate::error::{numbered_lines, Error};
+use std::ops::{Index, IndexMut};
+
+/// A row and a column, starting at 0 in the top left corner
+pub type Position = (usize, usize);
+
+/// The steps in rows and columns to the eight neighbours of a cell
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Which edges of the grid continue on the other side
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Wrap {
+    None,
+    /// Past the right edge is the left edge and the other way around
+    Horizontal,
+    /// Past the bottom edge is the top edge and the other way around
+    Vertical,
+    Both,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells row by row, there have to be `width * height` of them
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a grid needs a cell everywhere"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with a line for every row and a character for every cell. All rows have to
+    /// be as wide as the first one, `convert` gives the reason when it doesn't know a character.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        convert: impl Fn(char) -> Result<T, String>,
+    ) -> Result<Grid<T>, Error> {
+        let width = input
+            .lines()
+            .next()
+            .ok_or_else(|| Error::unexpected_end(day, "a grid"))?
+            .chars()
+            .count();
+        let mut cells = vec![];
+        let mut height = 0;
+        for (number, line) in numbered_lines(input) {
+            if line.chars().count() != width {
+                let reason = format!("expected a row of {} cells like the first row", width);
+                return Err(Error::invalid_line(day, number, line, reason));
+            }
+            for c in line.chars() {
+                cells.push(
+                    convert(c).map_err(|reason| Error::invalid_line(day, number, line, reason))?,
+                );
+            }
+            height += 1;
+        }
+        Ok(Grid::from_cells(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, column): Position) -> Option<&T> {
+        if row < self.height && column < self.width {
+            self.cells.get(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, column): Position) -> Option<&mut T> {
+        if row < self.height && column < self.width {
+            self.cells.get_mut(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    /// The position a step of rows and columns away, `None` when it is off the grid on an edge
+    /// that doesn't wrap
+    pub fn offset(
+        &self,
+        (row, column): Position,
+        (d_row, d_column): (isize, isize),
+        wrap: Wrap,
+    ) -> Option<Position> {
+        let wrap_rows = matches!(wrap, Wrap::Vertical | Wrap::Both);
+        let wrap_columns = matches!(wrap, Wrap::Horizontal | Wrap::Both);
+        Some((
+            step(row, d_row, self.height, wrap_rows)?,
+            step(column, d_column, self.width, wrap_columns)?,
+        ))
+    }
+
+    /// The positions of the neighbours of a cell that are on the grid, at most eight
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        DIRECTIONS
+            .iter()
+            .filter_map(move |&direction| self.offset(position, direction, Wrap::None))
+    }
+
+    /// Every position of the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell of the grid with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Creates a grid of the same size with every cell converted
+    pub fn map<U>(&self, mut convert: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(position, cell)| convert(position, cell))
+                .collect(),
+        }
+    }
+
+    /// Renders the grid back to text, with a line for every row
+    pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(&to_char).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn step(from: usize, by: isize, length: usize, wrap: bool) -> Option<usize> {
+    if wrap {
+        if length == 0 {
+            return None;
+        }
+        Some((from as isize + by).rem_euclid(length as isize) as usize)
+    } else {
+        let to = from as isize + by;
+        if 0 <= to && (to as usize) < length {
+            Some(to as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).expect("the position is off the grid")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .expect("the position is off the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Grid<bool>, Error> {
+        Grid::parse(0, input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("unknown cell {}", c)),
+        })
+    }
+
+    fn render(grid: &Grid<bool>) -> String {
+        grid.render(|&on| if on { '#' } else { '.' })
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = parse("#..\n.#.").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((1, 1)), Some(&true));
+        assert_eq!(grid.get((0, 1)), Some(&false));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(render(&grid), "#..\n.#.");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(parse(""), Err(Error::unexpected_end(0, "a grid")));
+        assert_eq!(
+            parse("#..\n.o."),
+            Err(Error::invalid_line(0, 2, ".o.", "unknown cell o"))
+        );
+        assert_eq!(
+            parse("#..\n.#"),
+            Err(Error::invalid_line(
+                0,
+                2,
+                ".#",
+                "expected a row of 3 cells like the first row"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_offset() {
+        let grid = parse("...\n...").unwrap();
+
+        assert_eq!(grid.offset((0, 1), (1, 1), Wrap::None), Some((1, 2)));
+        assert_eq!(grid.offset((0, 2), (0, 1), Wrap::None), None);
+        assert_eq!(grid.offset((0, 2), (0, 1), Wrap::Horizontal), Some((0, 0)));
+        assert_eq!(grid.offset((0, 0), (-1, 0), Wrap::Horizontal), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0), Wrap::Vertical), Some((1, 0)));
+        assert_eq!(grid.offset((1, 2), (3, 4), Wrap::Both), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse("...\n...\n...").unwrap();
+
+        assert_eq!(grid.neighbours((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_map_and_index() {
+        let mut grid = parse("#.\n.#").unwrap();
+        grid[(0, 1)] = true;
+
+        assert_eq!(render(&grid), "##\n.#");
+        assert_eq!(render(&grid.map(|(row, _), &on| on && row == 0)), "##\n..");
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, &on)| on)
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (1, 1)]
+        );
+    }
+}
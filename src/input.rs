@@ -0,0 +1,96 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where the puzzle input of a module is read from.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Source {
+    /// The input file belonging to the module: `input/<module>.txt`
+    Default,
+    /// A file given on the command line
+    Path(PathBuf),
+    /// Standard input, given on the command line as `-`
+    Stdin,
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    module: String,
+    location: String,
+    error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Could not read the input for {} from {}: {}",
+            self.module, self.location, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn default_path(module: &str) -> PathBuf {
+    Path::new("input").join(format!("{}.txt", module))
+}
+
+pub fn load(module: &str, source: &Source) -> Result<String, InputError> {
+    let (location, result) = match source {
+        Source::Default => {
+            let path = default_path(module);
+            (path.display().to_string(), fs::read_to_string(&path))
+        }
+        Source::Path(path) => (path.display().to_string(), fs::read_to_string(path)),
+        Source::Stdin => {
+            let mut input = String::new();
+            let result = io::stdin().read_to_string(&mut input).map(|_| input);
+            ("stdin".to_string(), result)
+        }
+    };
+    result.map_err(|error| InputError {
+        module: module.to_string(),
+        location,
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("other/day_13.txt"),
+            Source::Path(PathBuf::from("other/day_13.txt"))
+        );
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert_eq!(default_path("day_07"), PathBuf::from("input/day_07.txt"));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let source = Source::Path(PathBuf::from("input/does_not_exist.txt"));
+        let error = load("day_xx", &source).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Could not read the input for day_xx from input/does_not_exist.txt: "));
+    }
+}
@@ -0,0 +1,59 @@
+//! The solutions to Advent of Code 2020.
+//!
+//! Every day is a public module with the parsers and solvers of that day, next to `part_1` and
+//! `part_2` which solve a whole puzzle input, and the `EXAMPLES` from its puzzle description.
+//! `create_modules` registers all days for running them by name.
+extern crate itertools;
+extern crate regex;
+#[macro_use]
+extern crate lazy_static;
+extern crate num;
+extern crate num_derive;
+extern crate num_traits;
+extern crate petgraph;
+extern crate serde;
+extern crate serde_json;
+extern crate toml;
+
+pub mod answer;
+pub mod error;
+pub mod example;
+pub mod grid;
+pub mod input;
+pub mod modules;
+pub mod params;
+pub mod scaffold;
+pub mod verify;
+
+use modules::{Modules, Part};
+
+/// The `PARAMS` of a day, a day without them takes no parameters
+macro_rules! params_of {
+    ($mod:ident) => {
+        &[]
+    };
+    ($mod:ident $params:ident) => {
+        $mod::$params
+    };
+}
+
+macro_rules! modules {
+    ($($mod:ident => [$($part:ident),*] $($params:ident)?,)*) => {
+        $( pub mod $mod; )*
+
+        pub fn create_modules() -> Modules {
+            let mut modules = Modules::default();
+            $(
+                let parts = vec![$($mod::$part as Part),*];
+                let params = params_of!($mod $($params)?);
+                modules.add_module(stringify!($mod).to_string(), parts, $mod::EXAMPLES, params);
+            )*
+            modules
+        }
+    };
+}
+
+// the day modules and their parts are found by the build script
+include!(concat!(env!("OUT_DIR"), "/day_modules.rs"));
+
+day_modules!(modules);
@@ -1,55 +1,865 @@
-extern crate itertools;
-extern crate regex;
-#[macro_use]
-extern crate lazy_static;
-extern crate num;
-extern crate num_derive;
-extern crate num_traits;
-extern crate petgraph;
-
-use std::collections::HashMap;
+use advent_of_code_2020::modules::Modules;
+use advent_of_code_2020::params::Params;
+use advent_of_code_2020::{answer, create_modules, input, scaffold, verify};
 use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 fn main() {
-    let mut args = env::args();
-    args.next();
-
     let modules = create_modules();
-    while let Some(arg) = args.next() {
-        modules.run(&arg);
+    let arguments = match parse_args(env::args().skip(1), &modules.names()) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    if let Command::New { day } = arguments.command {
+        match scaffold::create_day(day) {
+            Ok(created) => {
+                for path in created {
+                    println!("Created {}", path.display());
+                }
+            }
+            Err(message) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if arguments.command == Command::List {
+        for name in modules.names() {
+            let parts = modules.parts(name).map_or(0, |parts| parts.len());
+            let plural = if parts == 1 { "" } else { "s" };
+            println!("{} ({} part{})", name, parts, plural);
+        }
+        return;
+    }
+
+    // check all modules exist before spending time on running any of them
+    for request in arguments.requests.iter() {
+        if let Err(message) = modules.parts(&request.module) {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+
+    let success = match arguments.command {
+        Command::Verify => verify(&modules, arguments.requests, arguments.jobs),
+        _ => run(
+            &modules,
+            arguments.requests,
+            arguments.format,
+            arguments.jobs,
+        ),
+    };
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn run(modules: &Modules, requests: Vec<Request>, format: answer::Format, jobs: usize) -> bool {
+    let mut printer = answer::Printer::new(format);
+    let mut success = true;
+    let mut parts = 0;
+    let mut total_time = Duration::default();
+    let start = Instant::now();
+    solve(modules, &requests, jobs, |_, solved| match solved {
+        Ok((answer, elapsed)) => {
+            for warning in answer.warnings.iter() {
+                eprintln!("{}", warning);
+            }
+            printer.print(&answer, elapsed);
+            parts += 1;
+            total_time += elapsed;
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            success = false;
+        }
+    });
+    let elapsed = start.elapsed();
+    printer.finish();
+    if jobs > 1 {
+        eprintln!(
+            "Solved {} parts in {:.2?} using {} jobs, the parts took {:.2?} together",
+            parts, elapsed, jobs, total_time
+        );
+    }
+    success
+}
+
+fn verify(modules: &Modules, requests: Vec<Request>, jobs: usize) -> bool {
+    let mut summary = verify::Summary::default();
+    let mut success = true;
+    let mut checked = Vec::new();
+    for request in requests {
+        // examples are checked against the answers from the description instead
+        if request.example {
+            checked.push((request, None));
+            continue;
+        }
+        match verify::RecordedAnswers::load(&request.module) {
+            Ok(recorded) => checked.push((request, Some(recorded))),
+            Err(message) => {
+                eprintln!("{}", message);
+                success = false;
+            }
+        }
     }
+    let (requests, recorded): (Vec<_>, Vec<_>) = checked.into_iter().unzip();
+    solve(modules, &requests, jobs, |unit, solved| match solved {
+        Ok((answer, _)) => {
+            let module = &requests[unit.request].module;
+            let part = answer.part as usize;
+            let (expected, name) = match unit.example {
+                Some(number) => (
+                    modules
+                        .examples(module)
+                        .ok()
+                        .and_then(|examples| examples[number - 1].answer(part)),
+                    format!("{} example {}", module, number),
+                ),
+                None => (
+                    recorded[unit.request]
+                        .as_ref()
+                        .and_then(|recorded| recorded.answer(part)),
+                    module.to_string(),
+                ),
+            };
+            let outcome = verify::check(expected, &answer);
+            println!("{} part {}: {}", name, part, outcome);
+            summary.add(&outcome);
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            success = false;
+        }
+    });
+    println!("{}", summary);
+    success && summary.failed == 0
 }
 
-type Run = fn();
+/// The answer to a part and the time it took, or why there is no answer
+type Solved = Result<(answer::Answer, Duration), String>;
 
-struct Modules {
-    runners: HashMap<String, Run>,
+/// A part to run, with the input and parameters to run it on
+struct Unit {
+    /// The index of the request the part was asked for in
+    request: usize,
+    /// The number of the example the part runs on, starting at 1, or `None` for a puzzle input
+    example: Option<usize>,
+    /// The number of the part, or why it can't be run
+    part: Result<usize, String>,
+    /// The index of the input the part runs on
+    input: usize,
+    params: Params,
 }
 
-impl Modules {
-    fn add_module(&mut self, name: String, func: Run) {
-        self.runners.insert(name, func);
+/// Runs the requested parts on `jobs` threads at the same time. Every result is handed to
+/// `on_solved` with the unit it belongs to, in the order of the requests and their parts.
+fn solve(
+    modules: &Modules,
+    requests: &[Request],
+    jobs: usize,
+    mut on_solved: impl FnMut(&Unit, Solved),
+) {
+    // read all inputs up front, stdin can only be read once
+    let mut inputs = Vec::new();
+    // every part that is run is a unit of work
+    let mut units = Vec::new();
+    for (index, request) in requests.iter().enumerate() {
+        let parts = match request.part {
+            Some(part) => part..=part,
+            None => {
+                1..=modules
+                    .parts(&request.module)
+                    .map_or(0, |parts| parts.len())
+            }
+        };
+        let unit = |example, input, part, params| Unit {
+            request: index,
+            example,
+            part,
+            input,
+            params,
+        };
+
+        if request.example {
+            // an example only covers the parts the description gives an answer for
+            let examples = modules.examples(&request.module).unwrap_or_default();
+            let mut covered = Vec::new();
+            for (number, example) in (1..).zip(examples) {
+                let mut params = example.params();
+                params.extend(&request.params);
+                covered.extend(
+                    parts
+                        .clone()
+                        .filter(|&part| example.answer(part).is_some())
+                        .map(|part| unit(Some(number), inputs.len(), Ok(part), params.clone())),
+                );
+                inputs.push(example.input.to_string());
+            }
+            if covered.is_empty() {
+                let parts = match request.part {
+                    Some(part) => format!("part {}", part),
+                    None => "any part".to_string(),
+                };
+                let message = format!("{} has no examples for {}", request.module, parts);
+                covered.push(unit(None, 0, Err(message), Params::new()));
+            }
+            units.extend(covered);
+            continue;
+        }
+
+        match input::load(&request.module, &request.source) {
+            Ok(input) => {
+                let params = &request.params;
+                units.extend(parts.map(|part| unit(None, inputs.len(), Ok(part), params.clone())));
+                inputs.push(input);
+            }
+            Err(error) => units.push(unit(None, 0, Err(error.to_string()), Params::new())),
+        }
     }
 
-    fn run(&self, name: &String) {
-        let runner = self.runners.get(name).unwrap();
-        runner();
+    let run_unit = |unit: &Unit| -> Solved {
+        let part = unit.part.clone()?;
+        catch_panic(part, || {
+            let mut solved = None;
+            modules.run(
+                &requests[unit.request].module,
+                Some(part),
+                &inputs[unit.input],
+                &unit.params,
+                |answer, elapsed| solved = Some((answer.clone(), elapsed)),
+            )?;
+            Ok(solved.expect("Running a part gives an answer"))
+        })
+    };
+
+    let next_unit = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let _quiet = QuietPanics::install();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(units.len()) {
+            let sender = sender.clone();
+            let (next_unit, units, run_unit) = (&next_unit, &units, &run_unit);
+            scope.spawn(move || loop {
+                let unit = next_unit.fetch_add(1, Ordering::Relaxed);
+                match units.get(unit) {
+                    Some(work) => sender.send((unit, run_unit(work))).unwrap(),
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+
+        // keep the results that finish early until the ones before them are handed out
+        let mut finished: Vec<Option<Solved>> = vec![None; units.len()];
+        let mut next_to_hand_out = 0;
+        for (unit, solved) in receiver {
+            finished[unit] = Some(solved);
+            while let Some(solved) = finished.get_mut(next_to_hand_out).and_then(Option::take) {
+                on_solved(&units[next_to_hand_out], solved);
+                next_to_hand_out += 1;
+            }
+        }
+    });
+}
+
+/// Silences the panic messages while it lives, `catch_panic` reports the panics of the parts
+/// instead. The previous hook is restored when it is dropped.
+struct QuietPanics(Option<PanicHook>);
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo) + Sync + Send + 'static>;
+
+impl QuietPanics {
+    fn install() -> QuietPanics {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        QuietPanics(Some(previous))
     }
 }
 
-macro_rules! modules {
-    ($($mod:ident,)*) => {
-        $( mod $mod; )*
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+/// Runs a part and turns a panic into an error, so only the part that panicked loses its answer
+fn catch_panic(part: usize, run: impl FnOnce() -> Solved) -> Solved {
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string());
+        Err(format!("part {} panicked: {}", part, message))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct Arguments {
+    command: Command,
+    format: answer::Format,
+    /// How many parts can run at the same time
+    jobs: usize,
+    requests: Vec<Request>,
+}
 
-        fn create_modules() -> Modules {
-            let mut modules = Modules { runners: HashMap::new() };
-            $( modules.add_module(stringify!($mod).to_string(), $mod::run); )*
-            modules
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Run the requested modules
+    Run,
+    /// List the registered modules
+    List,
+    /// Run the requested modules and compare their answers with the recorded answers
+    Verify,
+    /// Create the module and input file of a new day
+    New { day: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    module: String,
+    /// Only run this part of the module, or all of its parts when `None`
+    part: Option<usize>,
+    source: input::Source,
+    /// Solve the examples from the description instead of the input
+    example: bool,
+    params: Params,
+}
+
+impl Request {
+    fn new(module: String) -> Request {
+        Request {
+            module,
+            part: None,
+            source: input::Source::Default,
+            example: false,
+            params: Params::new(),
+        }
+    }
+}
+
+fn parse_args(args: impl Iterator<Item = String>, modules: &[&str]) -> Result<Arguments, String> {
+    // the first argument can be a subcommand, without one the modules are run
+    let mut args = args.peekable();
+    let command = match args.peek().map(String::as_str) {
+        Some("list") => {
+            args.next();
+            Command::List
+        }
+        Some("verify") => {
+            args.next();
+            Command::Verify
         }
+        Some("new") => {
+            args.next();
+            let day = args
+                .next()
+                .ok_or_else(|| "Expected the day to create after new".to_string())?;
+            if let Some(arg) = args.next() {
+                return Err(format!("Unexpected argument after new {}: {}", day, arg));
+            }
+            return Ok(Arguments {
+                command: Command::New {
+                    day: parse_day(&day)?,
+                },
+                format: answer::Format::Text,
+                jobs: 1,
+                requests: vec![],
+            });
+        }
+        _ => Command::Run,
+    };
+
+    // `--format <text|json|csv|table>` can be given anywhere to choose how the answers are
+    // printed, running `all` modules or a range of them defaults to a table
+    //
+    // `--jobs <n>` can be given anywhere to run up to n parts at the same time
+    //
+    // every selection of modules can be followed by options for those modules:
+    // - `--input <path>` to read the input from a different file, or from stdin when the path
+    //   is `-`
+    // - `--part <n>` to only run one of the parts, which can also be written as `<selection>:<n>`
+    // - `--example` to solve the examples from the description instead of the input
+    // - `--param <name>=<value>` to change a parameter of the puzzle, like the preamble of day 9
+    let mut format = None;
+    let mut jobs = 1;
+    let mut summarize = false;
+    let mut requests: Vec<Request> = Vec::new();
+    // the requests the options apply to start here
+    let mut selection_start = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--format" => {
+                format = Some(
+                    args.next()
+                        .ok_or_else(|| format!("Expected a value after {}", arg))?
+                        .parse()?,
+                );
+            }
+            "-j" | "--jobs" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a value after {}", arg))?;
+                jobs = match value.parse() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => return Err(format!("Expected at least 1 job, got: {}", value)),
+                };
+            }
+            "-e" | "--example" => {
+                if requests.is_empty() {
+                    return Err(format!("{} should follow the name of a module", arg));
+                }
+                for request in requests[selection_start..].iter_mut() {
+                    if request.source != input::Source::Default {
+                        return Err(format!(
+                            "Both an input and the examples given for {}",
+                            request.module
+                        ));
+                    }
+                    request.example = true;
+                }
+            }
+            "-P" | "--param" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a value after {}", arg))?;
+                if requests.is_empty() {
+                    return Err(format!("{} should follow the name of a module", arg));
+                }
+                let params = value.parse()?;
+                for request in requests[selection_start..].iter_mut() {
+                    request.params.extend(&params);
+                }
+            }
+            "-i" | "--input" | "-p" | "--part" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a value after {}", arg))?;
+                if requests.is_empty() {
+                    return Err(format!("{} should follow the name of a module", arg));
+                }
+                for request in requests[selection_start..].iter_mut() {
+                    match arg.as_str() {
+                        "-i" | "--input" => {
+                            if request.source != input::Source::Default {
+                                return Err(format!(
+                                    "More than one input given for {}",
+                                    request.module
+                                ));
+                            }
+                            if request.example {
+                                return Err(format!(
+                                    "Both an input and the examples given for {}",
+                                    request.module
+                                ));
+                            }
+                            request.source = input::Source::from_arg(&value);
+                        }
+                        _ => {
+                            if request.part.is_some() {
+                                return Err(format!(
+                                    "More than one part given for {}",
+                                    request.module
+                                ));
+                            }
+                            request.part = Some(parse_part(&value)?);
+                        }
+                    }
+                }
+            }
+            _ => {
+                let (selection, part) = match arg.split_once(':') {
+                    Some((selection, part)) => (selection, Some(parse_part(part)?)),
+                    None => (arg.as_str(), None),
+                };
+                summarize |= selection == "all" || selection.contains("..");
+                selection_start = requests.len();
+                for module in select_modules(selection, modules)? {
+                    let mut request = Request::new(module);
+                    request.part = part;
+                    requests.push(request);
+                }
+            }
+        }
+    }
+    // verifying checks everything unless told otherwise
+    if command == Command::Verify && requests.is_empty() {
+        requests = modules
+            .iter()
+            .map(|module| Request::new(module.to_string()))
+            .collect();
+    }
+    let format = format.unwrap_or(if summarize {
+        answer::Format::Table
+    } else {
+        answer::Format::Text
+    });
+    Ok(Arguments {
+        command,
+        format,
+        jobs,
+        requests,
+    })
+}
+
+/// Expands `all` and ranges of days like `5..=12` or `5..13` to the registered modules they
+/// contain, any other selection is taken as the name of a module.
+fn select_modules(selection: &str, modules: &[&str]) -> Result<Vec<String>, String> {
+    if selection == "all" {
+        return Ok(modules.iter().map(|module| module.to_string()).collect());
+    }
+
+    let range = match selection.split_once("..") {
+        Some((from, to)) => match to.strip_prefix('=') {
+            Some(to) => (from.parse(), to.parse()),
+            None => (from.parse(), to.parse().map(|to: u8| to.saturating_sub(1))),
+        },
+        None => return Ok(vec![selection.to_string()]),
     };
+    match range {
+        (Ok(from), Ok(to)) => {
+            let selected: Vec<_> = (from..=to)
+                .map(scaffold::module_name)
+                .filter(|module| modules.contains(&module.as_str()))
+                .collect();
+            if selected.is_empty() {
+                Err(format!("There are no modules in the range {}", selection))
+            } else {
+                Ok(selected)
+            }
+        }
+        _ => Err(format!(
+            "Expected a range of days like 5..=12, got: {}",
+            selection
+        )),
+    }
+}
+
+/// A day of the advent, either as a number or as the name of its module
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.trim_start_matches("day_").parse() {
+        Ok(number) if (1..=25).contains(&number) => Ok(number),
+        _ => Err(format!("Expected a day from 1 to 25, got: {}", day)),
+    }
+}
+
+fn parse_part(part: &str) -> Result<usize, String> {
+    match part.parse() {
+        Ok(part) if part > 0 => Ok(part),
+        _ => Err(format!(
+            "Expected a part number starting at 1, got: {}",
+            part
+        )),
+    }
 }
 
-modules![
-    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
-    day_13, day_14, day_15, day_16, day_17, day_18, day_19,
-];
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const MODULES: &[&str] = &["day_01", "day_02", "day_05", "day_06", "day_07"];
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(str::to_string)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let mut day_13 = Request::new("day_13".to_string());
+        day_13.source = input::Source::Path(PathBuf::from("other/day_13.txt"));
+        let mut day_15 = Request::new("day_15".to_string());
+        day_15.source = input::Source::Stdin;
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![Request::new("day_01".to_string()), day_13, day_15],
+        };
+
+        assert_eq!(
+            parse_args(
+                args("day_01 day_13 --input other/day_13.txt day_15 -i -"),
+                MODULES
+            ),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_args_parts() {
+        let mut day_15 = Request::new("day_15".to_string());
+        day_15.part = Some(1);
+        let mut day_16 = Request::new("day_16".to_string());
+        day_16.part = Some(2);
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![day_15, day_16],
+        };
+
+        assert_eq!(
+            parse_args(args("day_15:1 day_16 --part 2"), MODULES),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_args_format() {
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Json,
+            jobs: 1,
+            requests: vec![Request::new("day_01".to_string())],
+        };
+
+        assert_eq!(
+            parse_args(args("day_01 --format json"), MODULES),
+            Ok(expected)
+        );
+        assert!(parse_args(args("--format yaml day_01"), MODULES).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_all() {
+        let mut requests: Vec<_> = MODULES
+            .iter()
+            .map(|module| Request::new(module.to_string()))
+            .collect();
+        for request in requests.iter_mut() {
+            request.part = Some(1);
+        }
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Table,
+            jobs: 1,
+            requests,
+        };
+
+        assert_eq!(parse_args(args("all --part 1"), MODULES), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_args_range_with_format() {
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Csv,
+            jobs: 1,
+            requests: vec![
+                Request::new("day_02".to_string()),
+                Request::new("day_05".to_string()),
+                Request::new("day_06".to_string()),
+            ],
+        };
+
+        assert_eq!(parse_args(args("2..7 --format csv"), MODULES), Ok(expected));
+    }
+
+    #[test]
+    fn test_select_modules() {
+        assert_eq!(
+            select_modules("5..=7", MODULES),
+            Ok(vec![
+                "day_05".to_string(),
+                "day_06".to_string(),
+                "day_07".to_string()
+            ])
+        );
+        assert_eq!(
+            select_modules("1..2", MODULES),
+            Ok(vec!["day_01".to_string()])
+        );
+        assert_eq!(
+            select_modules("day_20", MODULES),
+            Ok(vec!["day_20".to_string()])
+        );
+        assert!(select_modules("8..=12", MODULES).is_err());
+        assert!(select_modules("a..b", MODULES).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_list() {
+        let expected = Arguments {
+            command: Command::List,
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![],
+        };
+
+        assert_eq!(parse_args(args("list"), MODULES), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_args_verify() {
+        let expected = Arguments {
+            command: Command::Verify,
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![Request::new("day_06".to_string())],
+        };
+
+        assert_eq!(parse_args(args("verify day_06"), MODULES), Ok(expected));
+        assert_eq!(
+            parse_args(args("verify"), MODULES).map(|arguments| arguments.requests.len()),
+            Ok(MODULES.len())
+        );
+    }
+
+    #[test]
+    fn test_parse_args_jobs() {
+        let expected = Arguments {
+            command: Command::Verify,
+            format: answer::Format::Text,
+            jobs: 4,
+            requests: vec![Request::new("day_06".to_string())],
+        };
+
+        assert_eq!(
+            parse_args(args("verify --jobs 4 day_06"), MODULES),
+            Ok(expected)
+        );
+        assert!(parse_args(args("all -j 0"), MODULES).is_err());
+    }
+
+    #[test]
+    fn test_solve_in_order() {
+        let modules = create_modules();
+        let mut day_01 = Request::new("day_01".to_string());
+        day_01.part = Some(2);
+        let mut missing = Request::new("day_02".to_string());
+        missing.source = input::Source::Path(PathBuf::from("input/does_not_exist.txt"));
+        let requests = vec![Request::new("day_06".to_string()), day_01, missing];
+
+        let mut solved = vec![];
+        solve(&modules, &requests, 3, |unit, result| {
+            solved.push((
+                unit.request,
+                result.map(|(answer, _)| (answer.day, answer.part)),
+            ))
+        });
+
+        assert_eq!(solved.len(), 4);
+        assert_eq!(solved[0], (0, Ok((6, 1))));
+        assert_eq!(solved[1], (0, Ok((6, 2))));
+        assert_eq!(solved[2], (1, Ok((1, 2))));
+        assert_eq!(solved[3].0, 2);
+        assert!(solved[3].1.is_err());
+    }
+
+    #[test]
+    fn test_solve_examples() {
+        let modules = create_modules();
+        let mut day_07 = Request::new("day_07".to_string());
+        day_07.example = true;
+        let mut day_19 = Request::new("day_19".to_string());
+        day_19.example = true;
+        day_19.part = Some(2);
+        let requests = vec![day_07, day_19];
+
+        let mut solved = vec![];
+        solve(&modules, &requests, 1, |unit, result| {
+            solved.push((
+                unit.example,
+                result.map(|(answer, _)| (answer.part, answer.value.to_string())),
+            ))
+        });
+
+        // the second example of day 7 is only for part 2
+        assert_eq!(solved.len(), 4);
+        assert_eq!(solved[0], (Some(1), Ok((1, "4".to_string()))));
+        assert_eq!(solved[1], (Some(1), Ok((2, "32".to_string()))));
+        assert_eq!(solved[2], (Some(2), Ok((2, "126".to_string()))));
+        assert_eq!(
+            solved[3],
+            (None, Err("day_19 has no examples for part 2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(
+            catch_panic(2, || panic!("No invalid number found")),
+            Err("part 2 panicked: No invalid number found".to_string())
+        );
+        assert_eq!(
+            catch_panic(1, || panic!("{} is not a number", "x")),
+            Err("part 1 panicked: x is not a number".to_string())
+        );
+        assert_eq!(
+            catch_panic(1, || Err("no input".to_string())),
+            Err("no input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_args_example_and_params() {
+        let mut day_09 = Request::new("day_09".to_string());
+        day_09.example = true;
+        day_09.params.set("preamble", "5");
+        let mut day_13 = Request::new("day_13".to_string());
+        day_13.params.set("start", "0");
+        let expected = Arguments {
+            command: Command::Run,
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![day_09, day_13],
+        };
+
+        assert_eq!(
+            parse_args(
+                args("day_09 --example --param preamble=5 day_13 -P start=0"),
+                MODULES
+            ),
+            Ok(expected)
+        );
+        assert!(parse_args(args("day_09 --param preamble"), MODULES).is_err());
+        assert!(parse_args(args("day_09 --input - --example"), MODULES).is_err());
+        assert!(parse_args(args("day_09 --example --input -"), MODULES).is_err());
+        assert!(parse_args(args("--example day_09"), MODULES).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_new() {
+        let expected = Arguments {
+            command: Command::New { day: 20 },
+            format: answer::Format::Text,
+            jobs: 1,
+            requests: vec![],
+        };
+
+        assert_eq!(parse_args(args("new 20"), MODULES), Ok(expected));
+        assert_eq!(
+            parse_args(args("new day_20"), MODULES).map(|arguments| arguments.command),
+            Ok(Command::New { day: 20 })
+        );
+        assert!(parse_args(args("new"), MODULES).is_err());
+        assert!(parse_args(args("new 26"), MODULES).is_err());
+        assert!(parse_args(args("new 20 21"), MODULES).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_invalid_part() {
+        assert!(parse_args(args("day_15:0"), MODULES).is_err());
+        assert!(parse_args(args("day_15 --part two"), MODULES).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_input_without_module() {
+        assert!(parse_args(args("--input day_01.txt day_01"), MODULES).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::example::Example;
+use crate::params::Params;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub type Part = fn(&str, &Params) -> Result<Answer, Error>;
+
+/// The registered modules and their parts, see `create_modules`.
+#[derive(Default)]
+pub struct Modules {
+    runners: HashMap<String, Vec<Part>>,
+    examples: HashMap<String, &'static [Example]>,
+    params: HashMap<String, &'static [&'static str]>,
+}
+
+impl Modules {
+    pub fn add_module(
+        &mut self,
+        name: String,
+        parts: Vec<Part>,
+        examples: &'static [Example],
+        params: &'static [&'static str],
+    ) {
+        self.examples.insert(name.clone(), examples);
+        self.params.insert(name.clone(), params);
+        self.runners.insert(name, parts);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.runners.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn parts(&self, name: &str) -> Result<&[Part], String> {
+        self.runners.get(name).map(Vec::as_slice).ok_or_else(|| {
+            let names = self.names();
+            let suggestion = closest_name(name, &names)
+                .map(|closest| format!(", did you mean {}?", closest))
+                .unwrap_or_default();
+            format!(
+                "Unknown module {}{}\nThe registered modules are: {}",
+                name,
+                suggestion,
+                names.join(", ")
+            )
+        })
+    }
+
+    pub fn examples(&self, name: &str) -> Result<&'static [Example], String> {
+        self.parts(name)?;
+        Ok(self.examples.get(name).copied().unwrap_or_default())
+    }
+
+    /// The names of the parameters a module takes
+    pub fn params(&self, name: &str) -> Result<&'static [&'static str], String> {
+        self.parts(name)?;
+        Ok(self.params.get(name).copied().unwrap_or_default())
+    }
+
+    /// Runs the requested parts of a module, handing each answer and the time it took to
+    /// `on_answer` as soon as it is known.
+    pub fn run(
+        &self,
+        name: &str,
+        part: Option<usize>,
+        input: &str,
+        params: &Params,
+        mut on_answer: impl FnMut(&Answer, Duration),
+    ) -> Result<(), String> {
+        let parts = self.parts(name)?;
+        let part_numbers = match part {
+            Some(part) if part > parts.len() => {
+                return Err(format!(
+                    "{} does not have a part {}, it has {}",
+                    name,
+                    part,
+                    parts.len()
+                ))
+            }
+            Some(part) => part..=part,
+            None => 1..=parts.len(),
+        };
+        // modules are named after their day: day_NN
+        let day = name.trim_start_matches("day_").parse().unwrap_or(0);
+        params
+            .check(day, self.params(name)?)
+            .map_err(|error| error.to_string())?;
+        for part in part_numbers {
+            let start = Instant::now();
+            let result = parts[part - 1](input, params);
+            let elapsed = start.elapsed();
+            let mut answer = result.map_err(|error| error.to_string())?;
+            answer.day = day;
+            answer.part = part as u8;
+            on_answer(&answer, elapsed);
+        }
+        Ok(())
+    }
+}
+
+/// Finds the name that takes the least edits to turn into `name`, as long as that is less than
+/// half of the name.
+fn closest_name<'a>(name: &str, names: &[&'a str]) -> Option<&'a str> {
+    names
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|&(distance, _)| distance * 2 < name.chars().count())
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // keep only the previous row of the distance matrix
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + if a_char == b_char { 0 } else { 1 };
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+            current.push(substitution.min(insertion).min(deletion));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("day_01", "day_01"), 0);
+        assert_eq!(edit_distance("day_1", "day_01"), 1);
+        assert_eq!(edit_distance("dya_01", "day_01"), 2);
+        assert_eq!(edit_distance("", "day"), 3);
+    }
+
+    #[test]
+    fn test_closest_name() {
+        let names = vec!["day_01", "day_02", "day_19"];
+
+        assert_eq!(closest_name("day_2", &names), Some("day_02"));
+        assert_eq!(closest_name("dya_19", &names), Some("day_19"));
+        assert_eq!(closest_name("list", &names), None);
+    }
+
+    #[test]
+    fn test_unknown_module() {
+        let modules = crate::create_modules();
+        let message = modules.parts("day_1").unwrap_err();
+
+        assert!(message.starts_with("Unknown module day_1, did you mean day_01?"));
+        assert!(message.contains("day_01, day_02, day_03"));
+    }
+
+    #[test]
+    fn test_examples() {
+        let modules = crate::create_modules();
+
+        assert_eq!(
+            modules
+                .examples("day_01")
+                .map(|examples| examples[0].answer(1)),
+            Ok(Some("514579"))
+        );
+        assert!(modules.examples("day_1").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        let modules = crate::create_modules();
+
+        assert_eq!(modules.params("day_09"), Ok(&["preamble"][..]));
+        assert_eq!(modules.params("day_06"), Ok(&[][..]));
+        // the examples only use the parameters their day takes
+        for name in modules.names() {
+            let params = modules.params(name).unwrap();
+            for example in modules.examples(name).unwrap() {
+                assert_eq!(example.params().check(0, params), Ok(()), "{}", name);
+            }
+        }
+        let message = modules
+            .run(
+                "day_09",
+                Some(1),
+                "1",
+                &"preambel=5".parse().unwrap(),
+                |_, _| {},
+            )
+            .unwrap_err();
+        assert_eq!(
+            message,
+            "Day 9: invalid value 5 for the parameter preambel. \
+             The parameters of day 9 are: preamble"
+        );
+    }
+}
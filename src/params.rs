@@ -0,0 +1,130 @@
+use crate::error::Error;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Named values that change what a part solves, like the size of the preamble on day 9. They
+/// are given on the command line with `--param <name>=<value>`, or by an example that differs
+/// from the real puzzle.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Adds the values of `other`, replacing the values with the same name.
+    pub fn extend(&mut self, other: &Params) {
+        self.values.extend(other.values.clone());
+    }
+
+    /// Checks that every given parameter is one of the names the day takes, a misspelled name
+    /// would otherwise be ignored.
+    pub fn check(&self, day: u8, names: &[&str]) -> Result<(), Error> {
+        match self
+            .values
+            .iter()
+            .find(|(name, _)| !names.contains(&name.as_str()))
+        {
+            Some((name, value)) => {
+                let reason = match names {
+                    [] => format!("Day {} takes no parameters", day),
+                    _ => format!("The parameters of day {} are: {}", day, names.join(", ")),
+                };
+                Err(Error::invalid_parameter_because(day, name, value, reason))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The value of a parameter, or `default` when it isn't given.
+    pub fn get<T: FromStr>(&self, day: u8, name: &str, default: T) -> Result<T, Error> {
+        match self.values.get(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::invalid_parameter(day, name, value)),
+            None => Ok(default),
+        }
+    }
+}
+
+impl From<&[(&str, &str)]> for Params {
+    fn from(values: &[(&str, &str)]) -> Params {
+        let mut params = Params::new();
+        for (name, value) in values {
+            params.set(*name, *value);
+        }
+        params
+    }
+}
+
+impl FromStr for Params {
+    type Err = String;
+
+    /// Parses a single `<name>=<value>` parameter
+    fn from_str(s: &str) -> Result<Params, String> {
+        match s.split_once('=') {
+            Some((name, value)) if !name.is_empty() => Ok(Params::from(&[(name, value)][..])),
+            _ => Err(format!("Expected a parameter like name=value, got: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let params = Params::from(&[("preamble", "5"), ("target", "many")][..]);
+
+        assert_eq!(params.get(9, "preamble", 25), Ok(5));
+        assert_eq!(params.get(9, "start", 0), Ok(0));
+        assert_eq!(
+            params.get(9, "target", 2020),
+            Err(Error::invalid_parameter(9, "target", "many"))
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let params = Params::from(&[("preamble", "5")][..]);
+
+        assert_eq!(params.check(9, &["preamble"]), Ok(()));
+        assert_eq!(Params::new().check(6, &[]), Ok(()));
+        assert_eq!(
+            params.check(6, &[]),
+            Err(Error::invalid_parameter_because(
+                6,
+                "preamble",
+                "5",
+                "Day 6 takes no parameters"
+            ))
+        );
+        assert_eq!(
+            params.check(1, &["target", "k"]),
+            Err(Error::invalid_parameter_because(
+                1,
+                "preamble",
+                "5",
+                "The parameters of day 1 are: target, k"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let mut expected = Params::new();
+        expected.set("target", "1000");
+
+        assert_eq!("target=1000".parse(), Ok(expected));
+        assert!("target".parse::<Params>().is_err());
+        assert!("=1000".parse::<Params>().is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::input;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("template.rs");
+
+/// The modules are named after their day: day_NN
+pub fn module_name(day: u8) -> String {
+    format!("day_{:02}", day)
+}
+
+pub fn module_path(module: &str) -> PathBuf {
+    Path::new("src").join(format!("{}.rs", module))
+}
+
+/// Creates the module of a new day from the template, together with an empty input file. The
+/// build script picks up the new module, so it can be run right away.
+///
+/// Returns the files that were created, an input file that already exists is left alone.
+pub fn create_day(day: u8) -> Result<Vec<PathBuf>, String> {
+    let module = module_name(day);
+    let mut created = Vec::new();
+
+    let path = module_path(&module);
+    match create_file(&path, &fill_template(day)) {
+        Ok(()) => created.push(path),
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+            return Err(format!("{} already exists", path.display()))
+        }
+        Err(error) => return Err(format!("Could not create {}: {}", path.display(), error)),
+    }
+
+    let path = input::default_path(&module);
+    match create_file(&path, "") {
+        Ok(()) => created.push(path),
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => (),
+        Err(error) => return Err(format!("Could not create {}: {}", path.display(), error)),
+    }
+    Ok(created)
+}
+
+fn fill_template(day: u8) -> String {
+    TEMPLATE.replace("xx", &day.to_string())
+}
+
+/// Writes a new file, without overwriting an existing one
+fn create_file(path: &Path, contents: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!(module_name(5), "day_05");
+        assert_eq!(module_path("day_05"), PathBuf::from("src/day_05.rs"));
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let module = fill_template(20);
+
+        assert!(module.starts_with("/// --- Day 20: _ ---"));
+        assert!(module.contains("const DAY: u8 = 20;"));
+        assert!(!module.contains("xx"));
+    }
+
+    #[test]
+    fn test_create_existing_day() {
+        assert_eq!(
+            create_day(1),
+            Err("src/day_01.rs already exists".to_string())
+        );
+    }
+}
@@ -1,10 +1,51 @@
 /// --- Day xx: _ ---
-/// 
+///
 /// Problem text
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error};
+use crate::example::Example;
+use crate::params::Params;
 
-const INPUT: &str = include_str!("../input/day_xx.txt");
+const DAY: u8 = xx;
 
-pub fn run() {
-    println!("Not implemented yet");
-    unimplemented!();
+/// The examples from the description, with the answers it gives for them
+pub const EXAMPLES: &[Example] = &[];
+
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let _lines = parse_input(input)?;
+
+    unimplemented!("Part 1 of day xx");
+}
+
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let _lines = parse_input(input)?;
+
+    unimplemented!("Part 2 of day xx");
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<&str>, Error> {
+    parse_lines(DAY, input, convert_to_line)
+}
+
+pub fn convert_to_line(line: &str) -> Result<&str, String> {
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(EXAMPLES, &[part_1, part_2]);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let input = "first line\nsecond line";
+        let expected = vec!["first line", "second line"];
+
+        assert_eq!(parse_input(input), Ok(expected));
+    }
 }
@@ -0,0 +1,176 @@
+use crate::answer::Answer;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The answers recorded for a module, the answer to part 1 is on the first line, part 2 on the
+/// second line and so on. Empty lines are parts without a recorded answer.
+#[derive(Debug, PartialEq)]
+pub struct RecordedAnswers {
+    answers: Vec<Option<String>>,
+}
+
+impl RecordedAnswers {
+    pub fn load(module: &str) -> Result<RecordedAnswers, String> {
+        let path = answers_path(module);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(RecordedAnswers::parse(&contents)),
+            // without a file all answers are missing
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                Ok(RecordedAnswers { answers: vec![] })
+            }
+            Err(error) => Err(format!(
+                "Could not read the answers for {} from {}: {}",
+                module,
+                path.display(),
+                error
+            )),
+        }
+    }
+
+    fn parse(contents: &str) -> RecordedAnswers {
+        let answers = contents
+            .lines()
+            .map(str::trim)
+            .map(|line| match line {
+                "" => None,
+                answer => Some(answer.to_string()),
+            })
+            .collect();
+        RecordedAnswers { answers }
+    }
+
+    /// The recorded answer to a part, parts start at 1
+    pub fn answer(&self, part: usize) -> Option<&str> {
+        part.checked_sub(1)
+            .and_then(|index| self.answers.get(index))
+            .and_then(Option::as_deref)
+    }
+
+    pub fn check(&self, answer: &Answer) -> Outcome {
+        check(self.answer(answer.part as usize), answer)
+    }
+}
+
+/// Compares an answer with the answer it is expected to be, if that is known
+pub fn check(expected: Option<&str>, answer: &Answer) -> Outcome {
+    let value = answer.value.to_string();
+    match expected {
+        Some(expected) if expected == value => Outcome::Pass,
+        Some(expected) => Outcome::Fail {
+            expected: expected.to_string(),
+            actual: value,
+        },
+        None => Outcome::Missing { actual: value },
+    }
+}
+
+pub fn answers_path(module: &str) -> PathBuf {
+    Path::new("answers").join(format!("{}.txt", module))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL, expected {} but got {}", expected, actual)
+            }
+            Outcome::Missing { actual } => write!(f, "missing, got {}", actual),
+        }
+    }
+}
+
+/// Keeps count of the outcomes of a verify run
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Fail { .. } => self.failed += 1,
+            Outcome::Missing { .. } => self.missing += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(part: u8, value: u32) -> Answer {
+        let mut answer = Answer::new("label", value);
+        answer.part = part;
+        answer
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = RecordedAnswers {
+            answers: vec![Some("933".to_string()), None, Some("711".to_string())],
+        };
+
+        assert_eq!(RecordedAnswers::parse("933\n\n 711 \n"), expected);
+    }
+
+    #[test]
+    fn test_check() {
+        let recorded = RecordedAnswers::parse("933\n\n");
+
+        assert_eq!(recorded.check(&answer(1, 933)), Outcome::Pass);
+        assert_eq!(
+            recorded.check(&answer(1, 934)),
+            Outcome::Fail {
+                expected: "933".to_string(),
+                actual: "934".to_string()
+            }
+        );
+        assert_eq!(
+            recorded.check(&answer(2, 711)),
+            Outcome::Missing {
+                actual: "711".to_string()
+            }
+        );
+        assert_eq!(
+            recorded.check(&answer(3, 1)),
+            Outcome::Missing {
+                actual: "1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut summary = Summary::default();
+        summary.add(&Outcome::Pass);
+        summary.add(&Outcome::Pass);
+        summary.add(&Outcome::Missing {
+            actual: "1".to_string(),
+        });
+
+        assert_eq!(summary.to_string(), "2 passed, 0 failed, 1 missing");
+    }
+}
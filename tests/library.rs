@@ -0,0 +1,81 @@
+use advent_of_code_2020::answer::Value;
+use advent_of_code_2020::params::Params;
+use advent_of_code_2020::{create_modules, day_07, day_13};
+
+#[test]
+fn test_bag_rules() {
+    let input = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+    let graph = day_07::parse_bag_rules(input).unwrap();
+
+    assert_eq!(day_07::count_containing_bags(&graph, "shiny gold"), 126);
+}
+
+#[test]
+fn test_contest_timestamp() {
+    let input = "939\n7,13,x,x,59,x,31,19\n";
+    let schedule = day_13::parse_bus_schedule_for_contest(input).unwrap();
+
+    assert_eq!(day_13::find_contest_timestamp(0, schedule), 1068781);
+}
+
+#[test]
+fn test_run_module_by_name() {
+    let modules = create_modules();
+    let mut answers = vec![];
+    modules
+        .run(
+            "day_13",
+            Some(1),
+            "939\n7,13,x,x,59,x,31,19\n",
+            &Params::new(),
+            |answer, _| answers.push(answer.clone()),
+        )
+        .unwrap();
+
+    assert_eq!(answers.len(), 1);
+    assert_eq!((answers[0].day, answers[0].part), (13, 1));
+    assert_eq!(answers[0].value, Value::Integer(295));
+}
+
+#[test]
+fn test_run_module_with_params() {
+    let modules = create_modules();
+    let example = &modules.examples("day_09").unwrap()[0];
+    let mut params = Params::new();
+    params.set("preamble", "5");
+    let mut answers = vec![];
+    modules
+        .run("day_09", None, example.input, &params, |answer, _| {
+            answers.push(answer.value.to_string())
+        })
+        .unwrap();
+
+    assert_eq!(answers, vec!["127", "62"]);
+}
+
+#[test]
+fn test_run_module_with_invalid_input() {
+    let modules = create_modules();
+    let result = modules.run(
+        "day_08",
+        None,
+        "nop +0\nadd +1\n",
+        &Params::new(),
+        |_, _| (),
+    );
+
+    assert_eq!(
+        result,
+        Err(
+            "Day 8, line 2: unknown operation add, expected nop, acc or jmp\n    add +1"
+                .to_string()
+        )
+    );
+}